@@ -1,13 +1,24 @@
+use crate::resolver::normalize_path;
+use crate::tsconfig::{load_tsconfig, TsConfig};
 use crate::types::{Config, DetectorError};
+use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// package.json のエントリーに対応するソースの拡張子
+const ENTRY_EXTENSIONS: &str = "{ts,tsx,mts,cts,js,jsx,mjs,cjs}";
+
+/// エントリーのパスから取り除く拡張子（長いものから判定する）
+const ENTRY_SUFFIXES: &[&str] = &[
+    ".d.mts", ".d.cts", ".d.ts", ".mts", ".cts", ".tsx", ".ts", ".mjs", ".cjs", ".jsx", ".js",
+];
 
 /// 設定ファイルを読み込む
 pub fn load_config(config_path: Option<&str>) -> Result<Config, DetectorError> {
     let config_path = config_path
         .map(|p| p.to_string())
-        .or_else(|| find_config_file())
-        .unwrap_or_else(|| "".to_string());
+        .or_else(find_config_file)
+        .unwrap_or_default();
 
     if config_path.is_empty() || !Path::new(&config_path).exists() {
         return Ok(Config::default());
@@ -40,7 +51,7 @@ fn find_config_file() -> Option<String> {
     }
 }
 
-/// モノレポ構造を検出してパスを調整
+/// モノレポ構造を検出してパスを調整し、package.json のエントリーを公開APIとして追加する
 pub fn adjust_config_for_monorepo(mut config: Config) -> Result<Config, DetectorError> {
     let package_json_path = "package.json";
 
@@ -49,7 +60,8 @@ pub fn adjust_config_for_monorepo(mut config: Config) -> Result<Config, Detector
     }
 
     let package_json_content = fs::read_to_string(package_json_path)?;
-    let package_json: serde_json::Value = serde_json::from_str(&package_json_content)?;
+    let package_json: Value = serde_json::from_str(&package_json_content)?;
+    let mut packages = vec![(PathBuf::new(), package_json.clone())];
 
     // モノレポ構造を検出
     let is_monorepo =
@@ -59,6 +71,7 @@ pub fn adjust_config_for_monorepo(mut config: Config) -> Result<Config, Detector
         let apps_dir = Path::new("apps");
         if apps_dir.exists() {
             let mut new_search_dirs = Vec::new();
+            let mut new_html_entry_files = Vec::new();
 
             // apps/* ディレクトリを検索
             for entry in fs::read_dir(apps_dir)? {
@@ -66,21 +79,121 @@ pub fn adjust_config_for_monorepo(mut config: Config) -> Result<Config, Detector
                 if entry.file_type()?.is_dir() {
                     let app_name = entry.file_name().to_string_lossy().to_string();
 
+                    let app_package_json = entry.path().join("package.json");
+                    if app_package_json.is_file() {
+                        let content = fs::read_to_string(&app_package_json)?;
+                        packages.push((entry.path(), serde_json::from_str(&content)?));
+                    }
+
                     for dir in &config.search_dirs {
                         new_search_dirs.push(format!("apps/{}/{}", app_name, dir));
                     }
+                    for html in &config.html_entry_files {
+                        new_html_entry_files.push(format!("apps/{}/{}", app_name, html));
+                    }
                 }
             }
 
             if !new_search_dirs.is_empty() {
                 config.search_dirs = new_search_dirs;
             }
+            config.html_entry_files.extend(new_html_entry_files);
         }
     }
 
+    for (package_dir, package_json) in &packages {
+        config
+            .package_entry_points
+            .extend(package_entry_points(package_dir, package_json));
+    }
+
     Ok(config)
 }
 
+/// package.json の `main`・`module`・`types`・`bin`・`exports` からエントリーのglobパターンを求める
+///
+/// tsconfig.json の `outDir` 配下を指すパスは `rootDir`（未指定なら `src`）配下のソースへ読み替える
+pub fn package_entry_points(package_dir: &Path, package_json: &Value) -> Vec<String> {
+    let mut targets = Vec::new();
+    for field in ["main", "module", "types", "typings"] {
+        if let Some(target) = package_json.get(field).and_then(|v| v.as_str()) {
+            targets.push(target);
+        }
+    }
+    match package_json.get("bin") {
+        Some(Value::String(target)) => targets.push(target),
+        Some(Value::Object(bins)) => targets.extend(bins.values().filter_map(|v| v.as_str())),
+        _ => {}
+    }
+    if let Some(exports) = package_json.get("exports") {
+        collect_export_targets(exports, &mut targets);
+    }
+
+    let tsconfig_path = package_dir.join("tsconfig.json");
+    let tsconfig = tsconfig_path
+        .is_file()
+        .then(|| load_tsconfig(&tsconfig_path).ok())
+        .flatten();
+
+    let mut patterns: Vec<String> = targets
+        .into_iter()
+        .filter_map(|target| entry_pattern(package_dir, target, tsconfig.as_ref()))
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+/// `exports` のサブパス・条件をすべて辿り、エクスポート先のパスを集める
+fn collect_export_targets<'a>(value: &'a Value, targets: &mut Vec<&'a str>) {
+    match value {
+        Value::String(target) => targets.push(target),
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_export_targets(item, targets)),
+        Value::Object(entries) => entries
+            .values()
+            .for_each(|entry| collect_export_targets(entry, targets)),
+        _ => {}
+    }
+}
+
+/// package.json 内のパスをソースファイルのglobパターンへ変換する
+fn entry_pattern(package_dir: &Path, target: &str, tsconfig: Option<&TsConfig>) -> Option<String> {
+    if Path::new(target).is_absolute() {
+        return None;
+    }
+
+    let mut path = normalize_path(&package_dir.join(target));
+    if let Some(tsconfig) = tsconfig {
+        let built = tsconfig
+            .out_dir
+            .as_ref()
+            .and_then(|out_dir| path.strip_prefix(out_dir).ok());
+        let source_dir = tsconfig.root_dir.clone().or_else(|| {
+            let src = tsconfig.config_dir.join("src");
+            src.is_dir().then_some(src)
+        });
+        if let (Some(relative), Some(source_dir)) = (built, source_dir) {
+            path = source_dir.join(relative);
+        }
+    }
+
+    let path = path.to_string_lossy().replace('\\', "/");
+    // スクリプト以外（`./package.json`、CSS等）は対象外
+    let stem = match ENTRY_SUFFIXES.iter().find_map(|suffix| path.strip_suffix(suffix)) {
+        Some(stem) => stem,
+        None if Path::new(&path).extension().is_none() => path.as_str(),
+        None => return None,
+    };
+    if stem.is_empty() {
+        return None;
+    }
+
+    // サブパスパターンの `*` はディレクトリをまたいで一致する
+    Some(format!("{}.{}", stem.replace('*', "**/*"), ENTRY_EXTENSIONS))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,11 +233,53 @@ mod tests {
 
     #[test]
     fn test_monorepo_adjustment() {
-        let mut config = Config::default();
-        config.search_dirs = vec!["src".to_string()];
+        let config = Config {
+            search_dirs: vec!["src".to_string()],
+            ..Default::default()
+        };
 
         // モノレポでない場合はそのまま
         let adjusted = adjust_config_for_monorepo(config.clone()).unwrap();
         assert_eq!(adjusted.search_dirs, vec!["src"]);
     }
+
+    #[test]
+    fn test_package_entry_points() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(
+            root.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "outDir": "./dist", "rootDir": "./lib" } }"#,
+        )
+        .unwrap();
+        let package_json: Value = serde_json::from_str(
+            r#"{
+  "main": "./dist/index.cjs",
+  "module": "./dist/index.mjs",
+  "types": "./dist/index.d.ts",
+  "bin": { "tool": "./bin/tool.js" },
+  "exports": {
+    ".": { "import": "./dist/index.mjs", "require": "./dist/index.cjs" },
+    "./hooks": { "types": "./dist/hooks/index.d.ts", "default": "./dist/hooks/index.js" },
+    "./icons/*": "./dist/icons/*.js",
+    "./package.json": "./package.json"
+  }
+}"#,
+        )
+        .unwrap();
+
+        let root_str = normalize_path(root).to_string_lossy().replace('\\', "/");
+        let patterns = package_entry_points(root, &package_json);
+        let expected: Vec<String> = [
+            "bin/tool",
+            "lib/hooks/index",
+            "lib/icons/**/*",
+            "lib/index",
+        ]
+        .iter()
+        .map(|stem| format!("{}/{}.{}", root_str, stem, ENTRY_EXTENSIONS))
+        .collect();
+        assert_eq!(patterns, expected);
+    }
 }
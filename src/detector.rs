@@ -1,18 +1,29 @@
+use crate::entry::EntryMatcher;
+use crate::html::html_entry_files;
+use crate::locals::{
+    classic_jsx_factory, import_bindings, local_declarations, pat_idents, LocalDeclaration,
+};
+use crate::location::{LineIndex, Position};
+use crate::resolver::{is_relative_specifier, normalize_path, ModuleResolver, Resolution};
+use crate::scope::Scopes;
+use crate::tsconfig;
 use crate::types::{
     Config, DetectionResult, DetectionStats, DetectorError, ElementInfo, ElementType,
     ElementUsage, Usage,
 };
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use swc_common::{BytePos, Span, Spanned};
 use swc_ecma_ast::*;
-use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_visit::{Visit, VisitWith};
 use walkdir::WalkDir;
 
 pub struct UnusedElementDetector {
     config: Config,
+    resolver: ModuleResolver,
 }
 
 #[derive(Debug, Clone)]
@@ -21,19 +32,208 @@ struct ElementDefinition {
     element_type: ElementType,
     file: String,
     should_ignore: bool,
+    /// ファイル内で最初に参照されている位置（`ElementType::Local` / `ElementType::Import` のみ）
+    local_usage: Option<Position>,
+    /// 定義の位置
+    position: Position,
+    /// 宣言全体の範囲（この中からの再帰的な参照は使用とみなさない）
+    body: Span,
+    /// `name` と異なるエクスポート名（default エクスポートなら `default`）
+    export_name: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 struct ElementReference {
     name: String,
     file: String,
-    line: usize,
+    /// import元の指定子（ファイル内の参照の場合は None）
+    source: Option<String>,
+    position: Position,
+    /// 参照のバイトオフセット（定義の範囲との比較に使う）
+    offset: BytePos,
     context: String,
 }
 
+/// 再エクスポート（`export ... from`）
+#[derive(Debug, Clone)]
+struct ReExport {
+    /// 再エクスポートしているファイル（バレル）
+    file: String,
+    /// 再エクスポート元の指定子
+    source: String,
+    kind: ReExportKind,
+    /// 再エクスポートしている位置
+    position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ReExportKind {
+    /// `export { imported as exported } from`
+    Named { imported: String, exported: String },
+    /// `export * from`
+    All,
+    /// `export * as exported from`
+    Namespace { exported: String },
+}
+
+/// ファイル単位の参照解析結果
+#[derive(Debug, Clone, Default)]
+struct FileReferences {
+    references: Vec<ElementReference>,
+    /// プロパティアクセス（`x.foo` の `foo`）。クラスメンバーの検出が有効な場合のみ収集する
+    properties: Vec<ElementReference>,
+    reexports: Vec<ReExport>,
+    /// ファイルが依存するモジュール（ファイル, 指定子）
+    dependencies: Vec<(String, String)>,
+}
+
+/// 使用状況の分析結果
+#[derive(Debug, Default)]
+struct UsageAnalysis {
+    unused: Vec<ElementInfo>,
+    used: Vec<ElementInfo>,
+    /// 定義ファイル内でのみ使われているエクスポート
+    locally_used: Vec<ElementInfo>,
+    notes: Vec<String>,
+}
+
+/// 再エクスポートを逆向きに辿るためのグラフ
+#[derive(Debug, Default)]
+struct ReExportGraph {
+    /// (元ファイル, 元の名前) → (バレル, エクスポート名, 位置)
+    named: HashMap<(String, String), Vec<ExportSite>>,
+    /// 元ファイル → `export *` しているバレルと位置
+    all: HashMap<String, Vec<(String, Position)>>,
+    /// 元ファイル → `export * as ns` しているバレルと名前、位置
+    namespace: HashMap<String, Vec<ExportSite>>,
+}
+
+/// エクスポートしている (ファイル, 名前, 位置)
+type ExportSite = (String, String, Position);
+
+impl ReExportGraph {
+    fn add(&mut self, reexport: &ReExport, target: String) {
+        let file = reexport.file.clone();
+        let position = reexport.position;
+        match &reexport.kind {
+            ReExportKind::Named { imported, exported } => self
+                .named
+                .entry((target, imported.clone()))
+                .or_default()
+                .push((file, exported.clone(), position)),
+            ReExportKind::All => self.all.entry(target).or_default().push((file, position)),
+            ReExportKind::Namespace { exported } => self
+                .namespace
+                .entry(target)
+                .or_default()
+                .push((file, exported.clone(), position)),
+        }
+    }
+
+    /// 定義 `(file, name)` がエクスポートされている全ての (ファイル, 名前, 位置) を返す（自身を含む）
+    ///
+    /// `export * as ns` で再エクスポートされたものは `ns.name` という名前で返す
+    ///
+    /// 位置は定義自身なら `position`、バレルなら再エクスポートしている文の位置
+    fn exported_as(&self, file: &str, name: &str, position: Position) -> Vec<ExportSite> {
+        let mut visited: HashMap<(String, String), Position> = HashMap::new();
+        let mut queue = vec![(file.to_string(), name.to_string(), position)];
+
+        while let Some((file, name, position)) = queue.pop() {
+            let node = (file, name);
+            if visited.contains_key(&node) {
+                continue;
+            }
+            let (file, name) = &node;
+
+            // `ns.name` は名前空間として再エクスポートされたメンバー（`ns` の再エクスポートを辿る）
+            let (head, member) = match name.split_once('.') {
+                Some((head, member)) => (head, Some(member)),
+                None => (name.as_str(), None),
+            };
+            if let Some(barrels) = self.named.get(&(file.clone(), head.to_string())) {
+                queue.extend(barrels.iter().map(|(barrel, exported, position)| {
+                    let exported = match member {
+                        Some(member) => format!("{}.{}", exported, member),
+                        None => exported.clone(),
+                    };
+                    (barrel.clone(), exported, *position)
+                }));
+            }
+            // `export *` は default を再エクスポートしない
+            if head != "default" {
+                if let Some(barrels) = self.all.get(file) {
+                    queue.extend(
+                        barrels
+                            .iter()
+                            .map(|(barrel, position)| (barrel.clone(), name.clone(), *position)),
+                    );
+                }
+            }
+            // `export * as ns` 経由の参照は `ns.name` へのメンバーアクセスで照合する
+            // （入れ子の名前空間は追跡せず、名前空間オブジェクトへの参照を全メンバーの使用とみなす）
+            if let Some(barrels) = self.namespace.get(file) {
+                queue.extend(barrels.iter().map(|(barrel, exported, position)| {
+                    let exported = match member {
+                        Some(_) => exported.clone(),
+                        None => format!("{}.{}", exported, name),
+                    };
+                    (barrel.clone(), exported, *position)
+                }));
+            }
+            visited.insert(node, position);
+        }
+
+        visited
+            .into_iter()
+            .map(|((file, name), position)| (file, name, position))
+            .collect()
+    }
+}
+
+/// ファイル間の依存関係（import・再エクスポート・`require()`・`import()`）
+#[derive(Debug, Default)]
+struct ModuleGraph {
+    /// ファイル → 依存先ファイル
+    edges: HashMap<String, Vec<String>>,
+}
+
+impl ModuleGraph {
+    /// 他のファイルから依存されているファイル
+    fn imported(&self) -> HashSet<&str> {
+        self.edges
+            .iter()
+            .flat_map(|(file, targets)| {
+                targets
+                    .iter()
+                    .filter(move |target| *target != file)
+                    .map(|target| target.as_str())
+            })
+            .collect()
+    }
+
+    /// `roots` から推移的に到達できるファイル
+    fn reachable_from<'a>(&self, roots: impl IntoIterator<Item = &'a String>) -> HashSet<String> {
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: Vec<String> = roots.into_iter().cloned().collect();
+
+        while let Some(file) = queue.pop() {
+            if !visited.insert(file.clone()) {
+                continue;
+            }
+            if let Some(targets) = self.edges.get(&file) {
+                queue.extend(targets.iter().filter(|t| !visited.contains(*t)).cloned());
+            }
+        }
+
+        visited
+    }
+}
+
 impl UnusedElementDetector {
     pub fn new(config: Config) -> Result<Self, DetectorError> {
         Ok(Self {
+            resolver: ModuleResolver::from_search_dirs(&config.search_dirs),
             config,
         })
     }
@@ -54,12 +254,33 @@ impl UnusedElementDetector {
             if self.config.detection_types.functions {
                 types.push("functions");
             }
+            if self.config.detection_types.hooks {
+                types.push("hooks");
+            }
             if self.config.detection_types.variables {
                 types.push("variables");
             }
             if self.config.detection_types.enums {
                 types.push("enums");
             }
+            if self.config.detection_types.enum_members {
+                types.push("enum members");
+            }
+            if self.config.detection_types.classes {
+                types.push("classes");
+            }
+            if self.config.detection_types.class_members {
+                types.push("class members");
+            }
+            if self.config.detection_types.locals {
+                types.push("locals");
+            }
+            if self.config.detection_types.imports {
+                types.push("imports");
+            }
+            if self.config.detection_types.files {
+                types.push("files");
+            }
             types
         };
 
@@ -76,20 +297,58 @@ impl UnusedElementDetector {
         println!("🔧 Discovered {} elements", definitions.len());
 
         // 3. AST解析で使用箇所を検索（全ファイルから）
-        let references = self.extract_references(&all_files)?;
+        let FileReferences {
+            references,
+            properties,
+            reexports,
+            dependencies,
+        } = self.extract_references(&all_files)?;
         println!("📄 Found {} references", references.len());
 
-        // 4. 使用状況を分析
-        let (unused, used) = self.analyze_usage(&definitions, &references)?;
+        // 4. エントリーポイントから到達可能なファイルを求める
+        let graph = self.build_module_graph(&dependencies);
+        let reachable = self.reachable_files(&graph, &all_files, &definition_files)?;
+        match &reachable {
+            Some(files) => println!(
+                "🧭 {} of {} files are reachable from entry points",
+                files.len(),
+                all_files.len()
+            ),
+            None => println!("🧭 No entry points matched; skipping reachability analysis"),
+        }
+
+        // 5. 使用状況を分析
+        let UsageAnalysis {
+            unused,
+            used,
+            locally_used,
+            notes,
+        } = self.analyze_usage(
+            &definitions,
+            &references,
+            &properties,
+            &reexports,
+            reachable.as_ref(),
+        )?;
+
+        // 6. 使われていないファイルを検出
+        let unused_files = if self.config.detection_types.files {
+            self.find_unused_files(&definition_files, &graph, reachable.as_ref())
+        } else {
+            Vec::new()
+        };
 
-        // 5. 統計情報を生成
-        let by_type = self.generate_statistics(&unused, &used);
+        // 7. 統計情報を生成
+        let by_type = self.generate_statistics(&unused, &used, &locally_used);
 
         Ok(DetectionResult {
             total: definitions.len(),
             unused,
             used,
+            locally_used,
             by_type,
+            unused_files,
+            notes,
         })
     }
 
@@ -99,7 +358,7 @@ impl UnusedElementDetector {
             .config
             .search_dirs
             .par_iter()
-            .map(|dir| self.get_files_in_dir(dir, &[".ts", ".tsx"]))
+            .map(|dir| self.get_files_in_dir(dir, &self.config.extensions))
             .collect::<Result<Vec<_>, _>>()?;
 
         let files: Vec<String> = files_nested.into_iter().flatten().collect();
@@ -112,7 +371,7 @@ impl UnusedElementDetector {
             .config
             .search_dirs
             .par_iter()
-            .map(|dir| self.get_files_in_dir_no_exclude(dir, &[".ts", ".tsx"]))
+            .map(|dir| self.get_files_in_dir_no_exclude(dir, &self.config.extensions))
             .collect::<Result<Vec<_>, _>>()?;
 
         let files: Vec<String> = files_nested.into_iter().flatten().collect();
@@ -124,7 +383,7 @@ impl UnusedElementDetector {
     fn get_files_in_dir(
         &self,
         dir: &str,
-        extensions: &[&str],
+        extensions: &[String],
     ) -> Result<Vec<String>, DetectorError> {
         if !Path::new(dir).exists() {
             return Ok(Vec::new());
@@ -148,9 +407,9 @@ impl UnusedElementDetector {
                     let ext_str = ext.to_string_lossy();
                     if extensions
                         .iter()
-                        .any(|&e| ext_str == e.trim_start_matches('.'))
+                        .any(|e| ext_str == e.trim_start_matches('.'))
                     {
-                        return Some(Ok(path.to_string_lossy().to_string()));
+                        return Some(Ok(normalize_path(path).to_string_lossy().to_string()));
                     }
                 }
 
@@ -165,7 +424,7 @@ impl UnusedElementDetector {
     fn get_files_in_dir_no_exclude(
         &self,
         dir: &str,
-        extensions: &[&str],
+        extensions: &[String],
     ) -> Result<Vec<String>, DetectorError> {
         if !Path::new(dir).exists() {
             return Ok(Vec::new());
@@ -185,9 +444,9 @@ impl UnusedElementDetector {
                     let ext_str = ext.to_string_lossy();
                     if extensions
                         .iter()
-                        .any(|&e| ext_str == e.trim_start_matches('.'))
+                        .any(|e| ext_str == e.trim_start_matches('.'))
                     {
-                        return Some(Ok(path.to_string_lossy().to_string()));
+                        return Some(Ok(normalize_path(path).to_string_lossy().to_string()));
                     }
                 }
 
@@ -234,7 +493,8 @@ impl UnusedElementDetector {
             .par_iter()
             .map(|file| {
                 let content = fs::read_to_string(file)?;
-                let defs = parse_file_for_definitions_static(file, &content, &config)?;
+                let tsconfig = self.resolver.tsconfig_for_file(file);
+                let defs = parse_file_for_definitions_static(file, &content, &config, tsconfig)?;
                 Ok(defs)
             })
             .collect::<Result<Vec<_>, DetectorError>>()?;
@@ -244,28 +504,186 @@ impl UnusedElementDetector {
 
 
     /// AST解析で参照を抽出
-    fn extract_references(&self, files: &[String]) -> Result<Vec<ElementReference>, DetectorError> {
-        let references: Vec<Vec<ElementReference>> = files
+    fn extract_references(&self, files: &[String]) -> Result<FileReferences, DetectorError> {
+        let config = &self.config;
+        let per_file: Vec<FileReferences> = files
             .par_iter()
             .map(|file| {
                 let content = fs::read_to_string(file)?;
-                let refs = parse_file_for_references_static(file, &content)?;
+                let refs = parse_file_for_references_static(file, &content, config)?;
                 Ok(refs)
             })
             .collect::<Result<Vec<_>, DetectorError>>()?;
 
-        Ok(references.into_iter().flatten().collect())
+        let mut merged = FileReferences::default();
+        for file_refs in per_file {
+            merged.references.extend(file_refs.references);
+            merged.properties.extend(file_refs.properties);
+            merged.reexports.extend(file_refs.reexports);
+            merged.dependencies.extend(file_refs.dependencies);
+        }
+        Ok(merged)
+    }
+
+
+    /// (ファイル, 指定子) の組をまとめて解決する
+    fn resolve_sources(&self, pairs: &[(&str, &str)]) -> Vec<Resolution> {
+        // 同じディレクトリからの同じ指定子は一度だけ解決する
+        let keys: HashSet<(&Path, &str)> = pairs
+            .iter()
+            .map(|(file, source)| (parent_dir(file), *source))
+            .collect();
+
+        let resolved: HashMap<(&Path, &str), Resolution> = keys
+            .into_par_iter()
+            .map(|(dir, source)| ((dir, source), self.resolver.resolve_from_dir(dir, source)))
+            .collect();
+
+        pairs
+            .iter()
+            .map(|(file, source)| resolved[&(parent_dir(file), *source)].clone())
+            .collect()
+    }
+
+    /// 参照のimport指定子をファイルへ解決する
+    fn resolve_references(&self, references: &[ElementReference]) -> Vec<Option<Resolution>> {
+        let pairs: Vec<(&str, &str)> = references
+            .iter()
+            .filter_map(|r| Some((r.file.as_str(), r.source.as_deref()?)))
+            .collect();
+        let mut resolved = self.resolve_sources(&pairs).into_iter();
+
+        references
+            .iter()
+            .map(|r| r.source.as_ref().and_then(|_| resolved.next()))
+            .collect()
+    }
+
+    /// 再エクスポートの逆引きグラフを構築する
+    fn build_reexport_graph(&self, reexports: &[ReExport]) -> ReExportGraph {
+        let pairs: Vec<(&str, &str)> = reexports
+            .iter()
+            .map(|r| (r.file.as_str(), r.source.as_str()))
+            .collect();
+
+        let mut graph = ReExportGraph::default();
+        for (reexport, resolution) in reexports.iter().zip(self.resolve_sources(&pairs)) {
+            if let Resolution::File(target) = resolution {
+                graph.add(reexport, target);
+            }
+        }
+        graph
+    }
+
+    /// ファイル間の依存グラフを構築する
+    fn build_module_graph(&self, dependencies: &[(String, String)]) -> ModuleGraph {
+        let pairs: Vec<(&str, &str)> = dependencies
+            .iter()
+            .map(|(file, source)| (file.as_str(), source.as_str()))
+            .collect();
+
+        let mut graph = ModuleGraph::default();
+        for ((file, _), resolution) in dependencies.iter().zip(self.resolve_sources(&pairs)) {
+            if let Resolution::File(target) = resolution {
+                graph.edges.entry(file.clone()).or_default().push(target);
+            }
+        }
+        graph
     }
 
+    /// エントリーポイントから推移的に到達可能なファイルを求める
+    ///
+    /// 公開APIのファイルと除外パターンに一致するファイル（テスト等）も起点に含める。
+    /// エントリーポイントが1つも見つからない場合は None（到達可能性を判定しない）
+    fn reachable_files(
+        &self,
+        graph: &ModuleGraph,
+        all_files: &[String],
+        definition_files: &[String],
+    ) -> Result<Option<HashSet<String>>, DetectorError> {
+        let roots = [
+            self.config.entry_points.as_slice(),
+            self.config.public_entry_points.as_slice(),
+        ]
+        .concat();
+        let entries = EntryMatcher::with_anchored(&roots, &self.config.package_entry_points)?
+            .with_files(html_entry_files(&self.config.html_entry_files));
+        let entry_files: Vec<&String> = all_files.iter().filter(|f| entries.is_entry(f)).collect();
+        if entry_files.is_empty() {
+            return Ok(None);
+        }
+
+        let definition_files: HashSet<&String> = definition_files.iter().collect();
+        let excluded_files = all_files.iter().filter(|f| !definition_files.contains(f));
+
+        Ok(Some(graph.reachable_from(entry_files.into_iter().chain(excluded_files))))
+    }
 
     /// 使用状況を分析
+    ///
+    /// `reachable` が与えられた場合、到達不能なファイル（デッドコード）からの参照は使用とみなさない
     fn analyze_usage(
         &self,
         definitions: &[ElementDefinition],
         references: &[ElementReference],
-    ) -> Result<(Vec<ElementInfo>, Vec<ElementInfo>), DetectorError> {
+        properties: &[ElementReference],
+        reexports: &[ReExport],
+        reachable: Option<&HashSet<String>>,
+    ) -> Result<UsageAnalysis, DetectorError> {
+        let resolutions = self.resolve_references(references);
+        let graph = self.build_reexport_graph(reexports);
+        let public_api = EntryMatcher::with_anchored(
+            &self.config.public_entry_points,
+            &self.config.package_entry_points,
+        )?;
+        let mut notes = Vec::new();
+
+        // (定義ファイル, エクスポート名) で参照を索引化
+        let mut by_target: HashMap<(String, &str), Vec<&ElementReference>> = HashMap::new();
+        // 解決できなかった非相対importは名前だけで照合する（エイリアス等の取りこぼし防止）
+        let mut by_name: HashMap<&str, Vec<&ElementReference>> = HashMap::new();
+        // ファイル内の参照（enumメンバーはファイル内での使用も数える）
+        let mut by_local: HashMap<(&str, &str), Vec<&ElementReference>> = HashMap::new();
+        // プロパティアクセス（クラスメンバーの判定に使う）
+        let mut by_property: HashMap<&str, Vec<&ElementReference>> = HashMap::new();
+        for property in properties {
+            by_property.entry(property.name.as_str()).or_default().push(property);
+        }
+
+        for (ref_item, resolution) in references.iter().zip(resolutions) {
+            match resolution {
+                Some(Resolution::File(target)) => {
+                    if ref_item.name == NAMESPACE_EXPORT {
+                        notes.push(format!(
+                            "{}: '{}' is used as a whole ({}); all exports of {} are treated as used",
+                            ref_item.file,
+                            ref_item.source.as_deref().unwrap_or_default(),
+                            ref_item.context,
+                            target
+                        ));
+                    }
+                    by_target
+                        .entry((target, ref_item.name.as_str()))
+                        .or_default()
+                        .push(ref_item);
+                }
+                Some(Resolution::Unresolved) => {
+                    let source = ref_item.source.as_deref().unwrap_or_default();
+                    if !is_relative_specifier(source) {
+                        by_name.entry(ref_item.name.as_str()).or_default().push(ref_item);
+                    }
+                }
+                None => by_local
+                    .entry((ref_item.file.as_str(), ref_item.name.as_str()))
+                    .or_default()
+                    .push(ref_item),
+                Some(Resolution::External) => {}
+            }
+        }
+
         let mut unused = Vec::new();
         let mut used = Vec::new();
+        let mut locally_used = Vec::new();
 
         for def in definitions {
             // Skip if element has ignore comment
@@ -273,49 +691,272 @@ impl UnusedElementDetector {
                 continue;
             }
 
-            let mut element_usages = Vec::new();
-            let mut is_used = false;
+            // モジュール内部の宣言・importはファイル内の参照だけで判定する
+            if matches!(def.element_type, ElementType::Local | ElementType::Import) {
+                let element_info = ElementInfo {
+                    name: def.name.clone(),
+                    element_type: def.element_type.clone(),
+                    definition_files: vec![def.file.clone()],
+                    line: Some(def.position.line),
+                    column: Some(def.position.column),
+                    export_name: None,
+                    usages: def.local_usage.map(|position| {
+                        vec![ElementUsage {
+                            file: def.file.clone(),
+                            usages: vec![Usage {
+                                line: position.line,
+                                column: position.column,
+                                context: "local".to_string(),
+                            }],
+                        }]
+                    }),
+                    reexported_from: Vec::new(),
+                };
+                if def.local_usage.is_some() {
+                    used.push(element_info);
+                } else {
+                    unused.push(element_info);
+                }
+                continue;
+            }
+
+            // enum・クラスのメンバーは `Owner.member` の形で定義されている
+            let (export_name, member) = match def.element_type {
+                ElementType::EnumMember | ElementType::ClassMember => match def.name.split_once('.') {
+                    Some((enum_name, member)) => (enum_name, Some(member)),
+                    None => (def.name.as_str(), None),
+                },
+                _ => (def.name.as_str(), None),
+            };
+
+            // default エクスポートは `default` として照合する
+            let export_name = def.export_name.as_deref().unwrap_or(export_name);
+
+            // バレル経由のエクスポートも含めて参照を集める
+            let exports = graph.exported_as(&def.file, export_name, def.position);
+            let mut reexported_from: Vec<String> = exports
+                .iter()
+                .filter(|(file, _, _)| *file != def.file)
+                .map(|(file, _, _)| file.clone())
+                .collect();
+            reexported_from.sort();
+            reexported_from.dedup();
+
+            // `export * as ns` の `ns` 自体が値として使われた場合は全メンバーを使用中とみなす
+            let namespace_escapes: Vec<&&ElementReference> = exports
+                .iter()
+                .filter_map(|(file, name, _)| {
+                    let (namespace, _) = name.split_once('.')?;
+                    by_target.get(&(file.clone(), namespace))
+                })
+                .flatten()
+                .filter(|ref_item| enum_object_escapes(&ref_item.context))
+                .collect();
+            if let Some(ref_item) = namespace_escapes.first() {
+                notes.push(format!(
+                    "{}: namespace re-export of '{}' is used as a value ({}); all its exports are treated as used",
+                    ref_item.file, def.file, ref_item.context
+                ));
+            }
 
-            for ref_item in references {
-                // 同じファイル内の定義は除外
-                if ref_item.file == def.file {
-                    continue;
+            let matched: Vec<&ElementReference> = match member {
+                None => exports
+                    .iter()
+                    .flat_map(|(file, name, _)| {
+                        // 名前空間オブジェクトごと使われている場合は全エクスポートが使用扱い
+                        [name.as_str(), NAMESPACE_EXPORT]
+                            .into_iter()
+                            .filter_map(|name| by_target.get(&(file.clone(), name)))
+                    })
+                    .flatten()
+                    .chain(namespace_escapes)
+                    // default import はローカル名が定義名と一致するとは限らないので、解決できたものだけ照合する
+                    .chain(
+                        by_name
+                            .get(def.name.as_str())
+                            .filter(|_| def.export_name.is_none())
+                            .into_iter()
+                            .flatten(),
+                    )
+                    // 同じファイル内の参照は除外
+                    .filter(|ref_item| ref_item.file != def.file)
+                    .copied()
+                    .collect(),
+                // クラス外からのプロパティアクセスを名前で照合する（型は追跡しない）
+                Some(member) if def.element_type == ElementType::ClassMember => {
+                    by_property.get(member).into_iter().flatten().copied().collect()
+                }
+                Some(member) => {
+                    let mut matched: Vec<&ElementReference> = Vec::new();
+                    let mut escaped: Vec<&ElementReference> = Vec::new();
+                    for (file, name, _) in &exports {
+                        let member_name = format!("{}.{}", name, member);
+                        for target_name in [member_name.as_str(), NAMESPACE_EXPORT] {
+                            matched.extend(by_target.get(&(file.clone(), target_name)).into_iter().flatten());
+                        }
+                        escaped.extend(by_target.get(&(file.clone(), name.as_str())).into_iter().flatten());
+                    }
+                    let file = def.file.as_str();
+                    matched.extend(by_name.get(def.name.as_str()).into_iter().flatten());
+                    matched.extend(by_local.get(&(file, def.name.as_str())).into_iter().flatten());
+                    escaped.extend(by_name.get(export_name).into_iter().flatten());
+                    escaped.extend(by_local.get(&(file, export_name)).into_iter().flatten());
+
+                    // enumオブジェクト自体が値として使われた場合（`Object.values(Status)` 等）は
+                    // 全メンバーを使用中とみなす
+                    escaped.retain(|ref_item| enum_object_escapes(&ref_item.context));
+                    if let Some(ref_item) = escaped.first() {
+                        notes.push(format!(
+                            "{}: enum '{}' is used as a value ({}); all its members are treated as used",
+                            ref_item.file, export_name, ref_item.context
+                        ));
+                    }
+                    matched.extend(escaped);
+                    matched
                 }
+            };
+
+            // package.json から公開されているエクスポートは常に使用中
+            let public_file = exports
+                .iter()
+                .find(|(file, _, _)| public_api.is_entry(file))
+                .map(|(file, _, position)| (file, *position));
+
+            let (live, dead): (Vec<&ElementReference>, Vec<&ElementReference>) = matched
+                .into_iter()
+                .partition(|ref_item| reachable.is_none_or(|files| files.contains(&ref_item.file)));
+            if live.is_empty() && !dead.is_empty() && public_file.is_none() {
+                let mut dead_files: Vec<&str> = dead.iter().map(|r| r.file.as_str()).collect();
+                dead_files.sort();
+                dead_files.dedup();
+                notes.push(format!(
+                    "{}: '{}' is only referenced from dead code ({})",
+                    def.file,
+                    def.name,
+                    dead_files.join(", ")
+                ));
+            }
 
-                if ref_item.name == def.name {
-                    is_used = true;
-                    element_usages.push(ElementUsage {
+            let element_usages: Vec<ElementUsage> = live
+                .into_iter()
+                .map(|ref_item| ElementUsage {
+                    file: ref_item.file.clone(),
+                    usages: vec![Usage {
+                        line: ref_item.position.line,
+                        column: ref_item.position.column,
+                        context: ref_item.context.clone(),
+                    }],
+                })
+                .chain(public_file.map(|(file, position)| ElementUsage {
+                    file: file.clone(),
+                    usages: vec![Usage {
+                        line: position.line,
+                        column: position.column,
+                        context: "public API".to_string(),
+                    }],
+                }))
+                .collect();
+            let is_used = !element_usages.is_empty();
+
+            // 外部から使われていないエクスポートでも、定義ファイル内で使われていれば
+            // export キーワードだけが不要
+            let local_usages: Vec<ElementUsage> = if is_used || member.is_some() {
+                Vec::new()
+            } else {
+                by_local
+                    .get(&(def.file.as_str(), def.name.as_str()))
+                    .into_iter()
+                    .flatten()
+                    // `export { foo }` 自体は使用ではない（`export default foo` は使用）
+                    .filter(|ref_item| ref_item.context != "export")
+                    // 再帰的な型・関数の自己参照は使用とみなさない
+                    .filter(|ref_item| {
+                        ref_item.offset < def.body.lo || ref_item.offset >= def.body.hi
+                    })
+                    .map(|ref_item| ElementUsage {
                         file: ref_item.file.clone(),
                         usages: vec![Usage {
-                            line: ref_item.line,
+                            line: ref_item.position.line,
+                            column: ref_item.position.column,
                             context: ref_item.context.clone(),
                         }],
-                    });
-                }
-            }
+                    })
+                    .collect()
+            };
+            let is_locally_used = !local_usages.is_empty();
 
             let element_info = ElementInfo {
                 name: def.name.clone(),
                 element_type: def.element_type.clone(),
                 definition_files: vec![def.file.clone()],
-                usages: if is_used { Some(element_usages) } else { None },
+                line: Some(def.position.line),
+                column: Some(def.position.column),
+                export_name: def.export_name.clone(),
+                usages: if is_used {
+                    Some(element_usages)
+                } else if is_locally_used {
+                    Some(local_usages)
+                } else {
+                    None
+                },
+                reexported_from,
             };
 
             if is_used {
                 used.push(element_info);
+            } else if is_locally_used {
+                locally_used.push(element_info);
             } else {
                 unused.push(element_info);
             }
         }
 
-        Ok((unused, used))
+        notes.sort();
+        notes.dedup();
+
+        Ok(UsageAnalysis {
+            unused,
+            used,
+            locally_used,
+            notes,
+        })
+    }
+
+    /// 使われていないファイルを検出
+    ///
+    /// エントリーポイントから到達できないファイル（`reachable` がない場合は、
+    /// 他のファイルから一度もimportされないファイル）を返す
+    fn find_unused_files(
+        &self,
+        files: &[String],
+        graph: &ModuleGraph,
+        reachable: Option<&HashSet<String>>,
+    ) -> Vec<String> {
+        let mut unused_files: Vec<String> = match reachable {
+            Some(reachable) => files
+                .iter()
+                .filter(|file| !reachable.contains(*file))
+                .cloned()
+                .collect(),
+            None => {
+                let imported = graph.imported();
+                files
+                    .iter()
+                    .filter(|file| !imported.contains(file.as_str()))
+                    .cloned()
+                    .collect()
+            }
+        };
+        unused_files.sort();
+        unused_files
     }
 
-    /// 統計情報を生成
+    /// 統計情報を生成（定義ファイル内でのみ使われているものは使用中として数える）
     fn generate_statistics(
         &self,
         unused: &[ElementInfo],
         used: &[ElementInfo],
+        locally_used: &[ElementInfo],
     ) -> HashMap<ElementType, DetectionStats> {
         let mut stats = HashMap::new();
 
@@ -331,7 +972,7 @@ impl UnusedElementDetector {
             entry.unused += 1;
         }
 
-        for item in used {
+        for item in used.iter().chain(locally_used) {
             let entry = stats
                 .entry(item.element_type.clone())
                 .or_insert(DetectionStats {
@@ -347,96 +988,332 @@ impl UnusedElementDetector {
     }
 }
 
+/// `class X extends React.Component` / `PureComponent` かどうか
+fn is_react_component_class(class: &Class) -> bool {
+    let Some(super_class) = &class.super_class else {
+        return false;
+    };
+    let name = match &**super_class {
+        Expr::Ident(ident) => ident.sym.as_ref(),
+        Expr::Member(member) => match (&*member.obj, &member.prop) {
+            (Expr::Ident(obj), MemberProp::Ident(prop)) if &*obj.sym == "React" => prop.sym.as_ref(),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    matches!(name, "Component" | "PureComponent")
+}
+
+/// 関数の本体（ブロック、またはアロー関数の式）
+enum CallableBody<'a> {
+    Block(&'a [Stmt]),
+    Expr(&'a Expr),
+}
+
+/// `use` に大文字が続くフックの命名かどうか
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_uppercase())
+}
+
+/// 他のフック（`useState()` / `React.useEffect()` など）を呼び出しているかどうか
+fn calls_hooks<N: VisitWith<HookCallFinder> + ?Sized>(node: &N) -> bool {
+    let mut finder = HookCallFinder { found: false };
+    node.visit_with(&mut finder);
+    finder.found
+}
+
+/// フックの呼び出しを探すVisitor（入れ子の関数・クラスは対象外）
+struct HookCallFinder {
+    found: bool,
+}
+
+impl Visit for HookCallFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            let callee_name = match &**callee {
+                Expr::Ident(ident) => Some(&ident.sym),
+                Expr::Member(member) => match &member.prop {
+                    MemberProp::Ident(prop) => Some(&prop.sym),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if callee_name.is_some_and(|name| is_hook_name(name)) {
+                self.found = true;
+                return;
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+/// 関数本体の `return` がJSX（または `createElement` の呼び出し）を返すかどうか
+fn returns_jsx(stmts: &[Stmt]) -> bool {
+    let mut finder = JsxReturnFinder { found: false };
+    stmts.visit_with(&mut finder);
+    finder.found
+}
+
+/// 式がJSX（または `createElement` の呼び出し）を含むかどうか
+fn contains_jsx(expr: &Expr) -> bool {
+    let mut finder = JsxFinder { found: false };
+    expr.visit_with(&mut finder);
+    finder.found
+}
+
+/// 関数本体の `return` 文からJSXを探すVisitor（入れ子の関数・クラスは対象外）
+struct JsxReturnFinder {
+    found: bool,
+}
+
+impl Visit for JsxReturnFinder {
+    fn visit_return_stmt(&mut self, stmt: &ReturnStmt) {
+        if let Some(arg) = &stmt.arg {
+            self.found |= contains_jsx(arg);
+        }
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+/// JSX要素・フラグメント・`createElement` の呼び出しを探すVisitor
+struct JsxFinder {
+    found: bool,
+}
+
+impl Visit for JsxFinder {
+    fn visit_jsx_element(&mut self, _: &JSXElement) {
+        self.found = true;
+    }
+
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+        self.found = true;
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            let is_create_element = match &**callee {
+                Expr::Ident(ident) => &*ident.sym == "createElement",
+                Expr::Member(member) => {
+                    matches!(&member.prop, MemberProp::Ident(prop) if &*prop.sym == "createElement")
+                }
+                _ => false,
+            };
+            if is_create_element {
+                self.found = true;
+                return;
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+/// クラス外から参照されうるメンバーか（private/protected・オーバーライドを除く）
+fn is_public_member(accessibility: Option<Accessibility>, is_override: bool) -> bool {
+    !is_override && matches!(accessibility, None | Some(Accessibility::Public))
+}
+
+/// enumの参照が、メンバーを特定できない値としての使用かどうか
+fn enum_object_escapes(context: &str) -> bool {
+    !matches!(
+        context,
+        "member" | "type" | "import" | "import type" | "export" | "export default"
+    )
+}
+
+/// ファイルの親ディレクトリを取得
+fn parent_dir(file: &str) -> &Path {
+    Path::new(file).parent().unwrap_or_else(|| Path::new(""))
+}
+
 /// 定義を収集するVisitor
 struct DefinitionVisitor {
     file: String,
     config: Config,
     definitions: Vec<ElementDefinition>,
-    content: String,
+    line_index: LineIndex,
+    /// クラシックJSXランタイムでJSXの変換先となる識別子（自動ランタイムなら None）
+    jsx_factory: Option<String>,
 }
 
 impl DefinitionVisitor {
-    fn new(file: String, config: &Config, content: String) -> Self {
+    fn new(
+        file: String,
+        config: &Config,
+        content: &str,
+        tsconfig: Option<&tsconfig::TsConfig>,
+    ) -> Self {
         Self {
             file,
             config: config.clone(),
             definitions: Vec::new(),
-            content,
+            line_index: LineIndex::new(content),
+            jsx_factory: classic_jsx_factory(content, tsconfig),
         }
     }
 
-    fn visit_module(&mut self, module: &Module) {
+    fn visit_module(&mut self, module: &Module, scopes: &Scopes) {
+        // エクスポートされていない文は locals（`local_declarations`）で扱う
         for item in &module.body {
-            self.visit_module_item(item);
+            if let ModuleItem::ModuleDecl(decl) = item {
+                self.visit_module_decl(module, decl);
+            }
         }
-    }
+        self.visit_commonjs_exports(module);
 
-    fn visit_module_item(&mut self, item: &ModuleItem) {
-        match item {
-            ModuleItem::ModuleDecl(decl) => self.visit_module_decl(decl),
-            ModuleItem::Stmt(stmt) => self.visit_stmt(stmt),
+        if self.config.detection_types.locals {
+            for local in local_declarations(module, scopes) {
+                self.push_local_definition(local, ElementType::Local);
+            }
+        }
+        if self.config.detection_types.imports {
+            for binding in import_bindings(module, scopes, self.jsx_factory.as_deref()) {
+                self.push_local_definition(binding, ElementType::Import);
+            }
         }
     }
 
-    fn visit_module_decl(&mut self, decl: &ModuleDecl) {
+    /// ファイル内の参照だけで使用状況が決まる定義を記録する
+    fn push_local_definition(&mut self, local: LocalDeclaration, element_type: ElementType) {
+        let should_ignore = self.has_ignore_comment(local.span);
+        self.definitions.push(ElementDefinition {
+            name: local.name,
+            element_type,
+            file: self.file.clone(),
+            should_ignore,
+            local_usage: local.usage.map(|span| self.line_index.position(span.lo)),
+            export_name: None,
+            position: self.line_index.position(local.span.lo),
+            body: local.span,
+        });
+    }
+
+    fn visit_module_decl(&mut self, module: &Module, decl: &ModuleDecl) {
         match decl {
             ModuleDecl::ExportDecl(export_decl) => {
                 self.visit_export_decl(&export_decl.decl);
             }
             ModuleDecl::ExportDefaultDecl(export_default) => {
+                let start = self.definitions.len();
                 self.visit_export_default_decl(export_default);
+                self.mark_default_export(start);
+            }
+            ModuleDecl::ExportDefaultExpr(export_default) => {
+                let start = self.definitions.len();
+                self.visit_export_default_expr(module, export_default);
+                self.mark_default_export(start);
+            }
+            // `export { Foo as default }`
+            ModuleDecl::ExportNamed(export) if export.src.is_none() => {
+                for specifier in &export.specifiers {
+                    let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(orig),
+                        exported: Some(exported),
+                        span,
+                        ..
+                    }) = specifier
+                    else {
+                        continue;
+                    };
+                    if module_export_name(exported) != "default" {
+                        continue;
+                    }
+                    let name = orig.sym.to_string();
+                    if let Some(element_type) = self.classify_local(module, &name, &name) {
+                        let start = self.definitions.len();
+                        self.push_definition(name, element_type, *span);
+                        self.mark_default_export(start);
+                    }
+                }
             }
             _ => {}
         }
     }
 
+    /// `start` 以降に追加した定義を default エクスポートとして扱う（クラスのメンバーは除く）
+    fn mark_default_export(&mut self, start: usize) {
+        for def in &mut self.definitions[start..] {
+            if def.element_type != ElementType::ClassMember {
+                def.export_name = Some("default".to_string());
+            }
+        }
+    }
+
+    /// 無名の default エクスポートの表示名（ファイル名。`index` ならディレクトリ名）
+    fn default_export_name(&self) -> String {
+        let path = Path::new(&self.file);
+        let stem = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let stem = stem.split('.').next().unwrap_or_default();
+        match path.parent().and_then(|dir| dir.file_name()) {
+            Some(dir) if stem == "index" => dir.to_string_lossy().to_string(),
+            _ => stem.to_string(),
+        }
+    }
+
     fn visit_export_decl(&mut self, decl: &Decl) {
         match decl {
-            Decl::Fn(func_decl) if self.config.detection_types.functions => {
+            Decl::Fn(func_decl) => {
                 if let Some(name) = self.extract_function_name(&func_decl.ident) {
-                    if self.is_camel_case(&name) {
-                        let should_ignore = self.has_ignore_comment(func_decl.span());
-                        self.definitions.push(ElementDefinition {
-                            name,
-                            element_type: ElementType::Function,
-                            file: self.file.clone(),
-                            should_ignore,
-                        });
+                    if let Some(element_type) = self.classify_fn(Some(&name), &func_decl.function) {
+                        self.push_definition(name, element_type, func_decl.span());
                     }
                 }
             }
             Decl::Var(var_decl) => {
                 for decl in &var_decl.decls {
-                    if let Pat::Ident(ident) = &decl.name {
-                        let name = ident.id.sym.to_string();
-
-                        if let Some(init) = &decl.init {
-                            let should_ignore = self.has_ignore_comment(decl.span);
-                            // コンポーネント検出
-                            if self.config.detection_types.components && self.is_component_pattern(&name, init) {
-                                self.definitions.push(ElementDefinition {
-                                    name: name.clone(),
-                                    element_type: ElementType::Component,
-                                    file: self.file.clone(),
-                                    should_ignore,
-                                });
-                            }
-                            // 関数検出
-                            else if self.config.detection_types.functions && self.is_function_pattern(init) && self.is_camel_case(&name) {
-                                self.definitions.push(ElementDefinition {
-                                    name: name.clone(),
-                                    element_type: ElementType::Function,
-                                    file: self.file.clone(),
-                                    should_ignore,
-                                });
+                    match &decl.name {
+                        Pat::Ident(ident) => {
+                            let name = ident.id.sym.to_string();
+
+                            let element_type = match &decl.init {
+                                Some(init) => self.classify_var(Some(&name), init),
+                                // `export let x;`
+                                None => self.detect_as(
+                                    &name,
+                                    ElementType::Variable,
+                                    self.config.detection_types.variables,
+                                ),
+                            };
+                            if let Some(element_type) = element_type {
+                                self.push_definition(name, element_type, decl.span);
                             }
-                            // 変数検出
-                            else if self.config.detection_types.variables && self.is_constant_case(&name) {
+                        }
+                        // `export const { a, b: c, ...rest } = obj` / `export const [x, y] = arr`
+                        pattern => {
+                            let should_ignore = self.has_ignore_comment(decl.span);
+                            for ident in pat_idents(pattern) {
+                                let name = ident.sym.to_string();
+                                let Some(element_type) = self.detect_as(
+                                    &name,
+                                    ElementType::Variable,
+                                    self.config.detection_types.variables,
+                                ) else {
+                                    continue;
+                                };
                                 self.definitions.push(ElementDefinition {
-                                    name: name.clone(),
-                                    element_type: ElementType::Variable,
+                                    name,
+                                    element_type,
                                     file: self.file.clone(),
-                                    should_ignore,
+                                    should_ignore: should_ignore || self.has_ignore_comment(ident.span),
+                                    local_usage: None,
+                                    export_name: None,
+                                    position: self.line_index.position(ident.span.lo),
+                                    body: decl.span,
                                 });
                             }
                         }
@@ -445,37 +1322,79 @@ impl DefinitionVisitor {
             }
             Decl::TsTypeAlias(type_alias) if self.config.detection_types.types => {
                 let name = type_alias.id.sym.to_string();
-                if self.is_pascal_case(&name) {
+                if self.follows_naming(&name, &ElementType::Type) {
                     let should_ignore = self.has_ignore_comment(type_alias.span());
                     self.definitions.push(ElementDefinition {
                         name,
                         element_type: ElementType::Type,
                         file: self.file.clone(),
                         should_ignore,
+                        local_usage: None,
+                        export_name: None,
+                        position: self.line_index.position(type_alias.id.span.lo),
+                        body: type_alias.span(),
                     });
                 }
             }
             Decl::TsInterface(interface) if self.config.detection_types.interfaces => {
                 let name = interface.id.sym.to_string();
-                if self.is_pascal_case(&name) {
+                if self.follows_naming(&name, &ElementType::Interface) {
                     let should_ignore = self.has_ignore_comment(interface.span());
                     self.definitions.push(ElementDefinition {
                         name,
                         element_type: ElementType::Interface,
                         file: self.file.clone(),
                         should_ignore,
+                        local_usage: None,
+                        export_name: None,
+                        position: self.line_index.position(interface.id.span.lo),
+                        body: interface.span(),
                     });
                 }
             }
-            Decl::TsEnum(enum_decl) if self.config.detection_types.enums => {
+            Decl::Class(class_decl) => {
+                self.visit_class(
+                    Some(class_decl.ident.sym.to_string()),
+                    class_decl.ident.span,
+                    &class_decl.class,
+                );
+            }
+            Decl::TsEnum(enum_decl) => {
                 let name = enum_decl.id.sym.to_string();
-                if self.is_pascal_case(&name) {
-                    let should_ignore = self.has_ignore_comment(enum_decl.span());
+                if !self.follows_naming(&name, &ElementType::Enum) {
+                    return;
+                }
+                let should_ignore = self.has_ignore_comment(enum_decl.span());
+
+                if self.config.detection_types.enum_members {
+                    for member in &enum_decl.members {
+                        let member_name = match &member.id {
+                            TsEnumMemberId::Ident(ident) => ident.sym.to_string(),
+                            TsEnumMemberId::Str(s) => s.value.to_string(),
+                        };
+                        self.definitions.push(ElementDefinition {
+                            name: format!("{}.{}", name, member_name),
+                            element_type: ElementType::EnumMember,
+                            file: self.file.clone(),
+                            should_ignore: should_ignore || self.has_ignore_comment(member.span),
+                            local_usage: None,
+                            export_name: None,
+                            position: self.line_index.position(member.span.lo),
+                            body: member.span,
+                        });
+                    }
+                }
+
+                if self.config.detection_types.enums {
                     self.definitions.push(ElementDefinition {
                         name,
                         element_type: ElementType::Enum,
                         file: self.file.clone(),
                         should_ignore,
+                        local_usage: None,
+                        export_name: None,
+                        position: self.line_index.position(enum_decl.id.span.lo),
+                        body: enum_decl.span(),
                     });
                 }
             }
@@ -485,54 +1404,409 @@ impl DefinitionVisitor {
 
     fn visit_export_default_decl(&mut self, export_default: &ExportDefaultDecl) {
         match &export_default.decl {
-            DefaultDecl::Fn(func_expr) if self.config.detection_types.components => {
-                if let Some(ident) = &func_expr.ident {
-                    let name = ident.sym.to_string();
-                    if self.is_pascal_case(&name) {
-                        let should_ignore = self.has_ignore_comment(export_default.span());
-                        self.definitions.push(ElementDefinition {
-                            name,
-                            element_type: ElementType::Component,
-                            file: self.file.clone(),
-                            should_ignore,
-                        });
-                    }
+            DefaultDecl::Fn(func_expr) => {
+                let name = func_expr.ident.as_ref().map(|ident| ident.sym.to_string());
+                if let Some(element_type) = self.classify_fn(name.as_deref(), &func_expr.function) {
+                    let name = name.unwrap_or_else(|| self.default_export_name());
+                    self.push_definition(name, element_type, export_default.span());
+                }
+            }
+            DefaultDecl::Class(class_expr) => {
+                let (name, span) = match &class_expr.ident {
+                    Some(ident) => (Some(ident.sym.to_string()), ident.span),
+                    None => (None, export_default.span()),
+                };
+                self.visit_class(name, span, &class_expr.class);
+            }
+            DefaultDecl::TsInterfaceDecl(interface) => {
+                let name = interface.id.sym.to_string();
+                if let Some(element_type) = self.detect_as(
+                    &name,
+                    ElementType::Interface,
+                    self.config.detection_types.interfaces,
+                ) {
+                    self.push_definition_with_body(
+                        name,
+                        element_type,
+                        interface.id.span,
+                        interface.span(),
+                    );
                 }
             }
-            _ => {}
         }
     }
 
-    fn visit_stmt(&mut self, _stmt: &Stmt) {
-        // Stmtの処理は必要に応じて実装
+    /// `export default Foo` / `export default memo(Foo)` / `export default () => ...`
+    fn visit_export_default_expr(&mut self, module: &Module, export_default: &ExportDefaultExpr) {
+        let (name, element_type) = match &*export_default.expr {
+            // ローカル宣言のエクスポート（importした束縛なら再エクスポートとして扱う）
+            Expr::Ident(ident) => {
+                let name = ident.sym.to_string();
+                let element_type = self.classify_local(module, &name, &name);
+                (name, element_type)
+            }
+            // `memo(Inner)` / `forwardRef(Inner)` は包んだコンポーネントの名前で表示する
+            expr if self.is_react_component_call(expr) => {
+                let name = self
+                    .wrapped_component_name(expr)
+                    .unwrap_or_else(|| self.default_export_name());
+                (name, self.classify_var(None, expr))
+            }
+            // 無名の値は形だけで種類を判定し、ファイル名は表示名にだけ使う
+            expr => (self.default_export_name(), self.classify_var(None, expr)),
+        };
+        if let Some(element_type) = element_type {
+            self.push_definition(name, element_type, export_default.span);
+        }
     }
 
-    // ヘルパーメソッド
-    fn extract_function_name(&self, ident: &Ident) -> Option<String> {
-        Some(ident.sym.to_string())
-    }
+    /// エクスポートされたクラスと、その公開メンバーを定義として記録する
+    ///
+    /// 無名の default エクスポート（`name` が None）は命名規則フィルタを適用せず、ファイル名で表示する
+    fn visit_class(&mut self, name: Option<String>, span: Span, class: &Class) {
+        if name
+            .as_deref()
+            .is_some_and(|name| !self.follows_naming(name, &ElementType::Class))
+        {
+            return;
+        }
+        let name = name.unwrap_or_else(|| self.default_export_name());
+        let should_ignore = self.has_ignore_comment(class.span);
 
-    fn is_component_pattern(&self, name: &str, expr: &Expr) -> bool {
-        self.is_pascal_case(name) && (
-            self.is_arrow_function(expr) ||
-            self.is_react_component_call(expr)
-        )
-    }
+        // `class X extends React.Component` はコンポーネント（メソッドはReactが呼び出す）
+        if is_react_component_class(class) {
+            if self.config.detection_types.components {
+                self.push_class_definition(
+                    name,
+                    ElementType::Component,
+                    should_ignore,
+                    span,
+                    class.span,
+                );
+            }
+            return;
+        }
+
+        if self.config.detection_types.class_members {
+            let mut seen = HashSet::new();
+            let members: Vec<(String, Span)> = class
+                .body
+                .iter()
+                .filter_map(|member| match member {
+                    ClassMember::Method(method)
+                        if is_public_member(method.accessibility, method.is_override) =>
+                    {
+                        Some((prop_name(&method.key)?, method.span))
+                    }
+                    ClassMember::ClassProp(prop)
+                        if is_public_member(prop.accessibility, prop.is_override) =>
+                    {
+                        Some((prop_name(&prop.key)?, prop.span))
+                    }
+                    _ => None,
+                })
+                // getter/setter やオーバーロードは同じ名前で1つとして扱う（隣接していなくてもよい）
+                .filter(|(member, _)| seen.insert(member.clone()))
+                .collect();
+
+            for (member, span) in members {
+                let should_ignore = should_ignore || self.has_ignore_comment(span);
+                self.push_class_definition(
+                    format!("{}.{}", name, member),
+                    ElementType::ClassMember,
+                    should_ignore,
+                    span,
+                    span,
+                );
+            }
+        }
 
-    fn is_function_pattern(&self, expr: &Expr) -> bool {
-        self.is_arrow_function(expr)
+        if self.config.detection_types.classes {
+            self.push_class_definition(name, ElementType::Class, should_ignore, span, class.span);
+        }
     }
 
-    fn is_arrow_function(&self, expr: &Expr) -> bool {
-        matches!(expr, Expr::Arrow(_))
+    fn push_class_definition(
+        &mut self,
+        name: String,
+        element_type: ElementType,
+        should_ignore: bool,
+        span: Span,
+        body: Span,
+    ) {
+        self.definitions.push(ElementDefinition {
+            name,
+            element_type,
+            file: self.file.clone(),
+            should_ignore,
+            local_usage: None,
+            export_name: None,
+            position: self.line_index.position(span.lo),
+            body,
+        });
     }
 
-    fn is_react_component_call(&self, expr: &Expr) -> bool {
-        match expr {
-            Expr::Call(call_expr) => {
-                if let Callee::Expr(callee) = &call_expr.callee {
-                    match &**callee {
-                        Expr::Ident(ident) => {
+    /// CommonJS の `module.exports` / `exports.x` への代入をエクスポートとして収集
+    fn visit_commonjs_exports(&mut self, module: &Module) {
+        for item in &module.body {
+            let ModuleItem::Stmt(Stmt::Expr(expr_stmt)) = item else {
+                continue;
+            };
+            let Expr::Assign(assign) = &*expr_stmt.expr else {
+                continue;
+            };
+            let AssignTarget::Simple(SimpleAssignTarget::Member(target)) = &assign.left else {
+                continue;
+            };
+
+            match commonjs_export_target(target) {
+                // `module.exports = { a, b: c, d() {} }`
+                Some(None) => match &*assign.right {
+                    Expr::Object(object) => {
+                        for prop in &object.props {
+                            let PropOrSpread::Prop(prop) = prop else {
+                                continue;
+                            };
+                            let (name, element_type) = match &**prop {
+                                Prop::Shorthand(ident) => {
+                                    let name = ident.sym.to_string();
+                                    let element_type = self.classify_local(module, &name, &name);
+                                    (name, element_type)
+                                }
+                                Prop::KeyValue(key_value) => {
+                                    let Some(name) = prop_name(&key_value.key) else {
+                                        continue;
+                                    };
+                                    let element_type =
+                                        self.classify_commonjs_value(module, &name, &key_value.value);
+                                    (name, element_type)
+                                }
+                                Prop::Method(method) => {
+                                    let Some(name) = prop_name(&method.key) else {
+                                        continue;
+                                    };
+                                    let element_type = self.classify_fn(Some(&name), &method.function);
+                                    (name, element_type)
+                                }
+                                _ => continue,
+                            };
+                            if let Some(element_type) = element_type {
+                                self.push_definition(name, element_type, prop.span());
+                            }
+                        }
+                    }
+                    // `module.exports = foo` / `module.exports = function foo() {}`
+                    value => {
+                        let name = match value {
+                            Expr::Ident(ident) => Some(ident.sym.to_string()),
+                            Expr::Fn(func) => func.ident.as_ref().map(|ident| ident.sym.to_string()),
+                            _ => None,
+                        };
+                        if let Some(name) = name {
+                            if let Some(element_type) = self.classify_commonjs_value(module, &name, value) {
+                                self.push_definition(name, element_type, assign.span);
+                            }
+                        }
+                    }
+                },
+                // `module.exports.foo = ...` / `exports.foo = ...`
+                Some(Some(name)) => {
+                    if let Some(element_type) = self.classify_commonjs_value(module, &name, &assign.right) {
+                        self.push_definition(name, element_type, assign.span);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// CommonJS でエクスポートされる値の種類を判定
+    fn classify_commonjs_value(&self, module: &Module, name: &str, value: &Expr) -> Option<ElementType> {
+        match value {
+            Expr::Ident(ident) => self.classify_local(module, name, &ident.sym),
+            Expr::Fn(func) => self.classify_fn(Some(name), &func.function),
+            _ => self.classify_var(Some(name), value),
+        }
+    }
+
+    /// トップレベルのローカル宣言 `local` を `name` としてエクスポートした場合の種類を判定
+    fn classify_local(&self, module: &Module, name: &str, local: &str) -> Option<ElementType> {
+        let detection_types = &self.config.detection_types;
+        let decls = module.body.iter().filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => Some(&export_decl.decl),
+            _ => None,
+        });
+        for decl in decls {
+            let element_type = match decl {
+                Decl::Fn(func) if &*func.ident.sym == local => self.classify_fn(Some(name), &func.function),
+                Decl::Class(class_decl) if &*class_decl.ident.sym == local => {
+                    if is_react_component_class(&class_decl.class) {
+                        self.detect_as(name, ElementType::Component, detection_types.components)
+                    } else {
+                        self.detect_as(name, ElementType::Class, detection_types.classes)
+                    }
+                }
+                Decl::Var(var_decl) => {
+                    let init = var_decl.decls.iter().find_map(|decl| match (&decl.name, &decl.init) {
+                        (Pat::Ident(ident), Some(init)) if &*ident.id.sym == local => Some(init),
+                        _ => None,
+                    });
+                    let Some(init) = init else {
+                        continue;
+                    };
+                    self.classify_var(Some(name), init)
+                }
+                _ => continue,
+            };
+            return element_type;
+        }
+        None
+    }
+
+    /// 関数宣言の種類を判定（`name` が None なら無名の default エクスポート）
+    fn classify_fn(&self, name: Option<&str>, function: &Function) -> Option<ElementType> {
+        let stmts = function.body.as_ref().map_or(&[][..], |body| &body.stmts[..]);
+        self.classify_callable(name, CallableBody::Block(stmts))
+    }
+
+    /// 関数（宣言・アロー関数・関数式）の種類を名前と本体から判定
+    ///
+    /// `use[A-Z]` で始まり他のフックを呼び出していればフック、
+    /// JSXを返すPascalCaseならコンポーネント、それ以外は関数。
+    /// 無名（`name` が None）ならJSXを返すかどうかだけで判定する
+    fn classify_callable(&self, name: Option<&str>, body: CallableBody) -> Option<ElementType> {
+        let (returns_jsx, calls_hooks) = match body {
+            CallableBody::Block(stmts) => (returns_jsx(stmts), calls_hooks(stmts)),
+            CallableBody::Expr(expr) => (contains_jsx(expr), calls_hooks(expr)),
+        };
+
+        let detection_types = &self.config.detection_types;
+        if name.is_some_and(is_hook_name) && calls_hooks {
+            self.detect_as_maybe_anonymous(name, ElementType::Hook, detection_types.hooks)
+        } else if name.is_none_or(|name| self.is_pascal_case(name)) && returns_jsx {
+            self.detect_as_maybe_anonymous(name, ElementType::Component, detection_types.components)
+        } else {
+            self.detect_as_maybe_anonymous(name, ElementType::Function, detection_types.functions)
+        }
+    }
+
+    /// 変数宣言の種類を初期化式の形から判定
+    ///
+    /// 関数式はフック・コンポーネント・関数、`memo()` / `forwardRef()` はコンポーネント、
+    /// クラス式はクラス、それ以外（オブジェクト・呼び出し・リテラル等）は変数とする
+    fn classify_var(&self, name: Option<&str>, init: &Expr) -> Option<ElementType> {
+        match init {
+            Expr::Arrow(arrow) => {
+                let body = match &*arrow.body {
+                    BlockStmtOrExpr::BlockStmt(block) => CallableBody::Block(&block.stmts),
+                    BlockStmtOrExpr::Expr(expr) => CallableBody::Expr(expr),
+                };
+                return self.classify_callable(name, body);
+            }
+            Expr::Fn(func) => return self.classify_fn(name, &func.function),
+            Expr::Paren(paren) => return self.classify_var(name, &paren.expr),
+            Expr::TsAs(ts_as) => return self.classify_var(name, &ts_as.expr),
+            Expr::TsSatisfies(satisfies) => return self.classify_var(name, &satisfies.expr),
+            _ => {}
+        }
+
+        let detection_types = &self.config.detection_types;
+        match init {
+            Expr::Class(class_expr) if is_react_component_class(&class_expr.class) => {
+                self.detect_as_maybe_anonymous(name, ElementType::Component, detection_types.components)
+            }
+            Expr::Class(_) => self.detect_as_maybe_anonymous(name, ElementType::Class, detection_types.classes),
+            init if self.is_react_component_call(init) => {
+                self.detect_as_maybe_anonymous(name, ElementType::Component, detection_types.components)
+            }
+            _ => self.detect_as_maybe_anonymous(name, ElementType::Variable, detection_types.variables),
+        }
+    }
+
+    /// 検出対象の種類で、命名規則フィルタにも合う場合のみ種類を返す
+    fn detect_as(&self, name: &str, element_type: ElementType, enabled: bool) -> Option<ElementType> {
+        (enabled && self.follows_naming(name, &element_type)).then_some(element_type)
+    }
+
+    /// `detect_as` と同じだが、無名（`name` が None）なら命名規則フィルタを適用しない
+    fn detect_as_maybe_anonymous(
+        &self,
+        name: Option<&str>,
+        element_type: ElementType,
+        enabled: bool,
+    ) -> Option<ElementType> {
+        match name {
+            Some(name) => self.detect_as(name, element_type, enabled),
+            None => enabled.then_some(element_type),
+        }
+    }
+
+    /// `naming_filter` が有効な場合、種類ごとの命名規則に合っているかどうか
+    fn follows_naming(&self, name: &str, element_type: &ElementType) -> bool {
+        if !self.config.naming_filter {
+            return true;
+        }
+        match element_type {
+            ElementType::Component
+            | ElementType::Type
+            | ElementType::Interface
+            | ElementType::Enum
+            | ElementType::Class => self.is_pascal_case(name),
+            ElementType::Function | ElementType::Hook => self.is_camel_case(name),
+            ElementType::Variable => self.is_constant_case(name),
+            _ => true,
+        }
+    }
+
+    fn push_definition(&mut self, name: String, element_type: ElementType, span: Span) {
+        self.push_definition_with_body(name, element_type, span, span);
+    }
+
+    /// 位置（`span`）と宣言全体（`body`）が異なる定義を記録する
+    fn push_definition_with_body(
+        &mut self,
+        name: String,
+        element_type: ElementType,
+        span: Span,
+        body: Span,
+    ) {
+        let should_ignore = self.has_ignore_comment(span);
+        self.definitions.push(ElementDefinition {
+            name,
+            element_type,
+            file: self.file.clone(),
+            should_ignore,
+            local_usage: None,
+            export_name: None,
+            position: self.line_index.position(span.lo),
+            body,
+        });
+    }
+
+    // ヘルパーメソッド
+    fn extract_function_name(&self, ident: &Ident) -> Option<String> {
+        Some(ident.sym.to_string())
+    }
+
+    /// `memo(Inner)` / `memo(forwardRef(Inner))` が包んでいるコンポーネントの名前
+    fn wrapped_component_name(&self, expr: &Expr) -> Option<String> {
+        let Expr::Call(call) = expr else {
+            return None;
+        };
+        match &*call.args.first()?.expr {
+            Expr::Ident(ident) => Some(ident.sym.to_string()),
+            inner if self.is_react_component_call(inner) => self.wrapped_component_name(inner),
+            _ => None,
+        }
+    }
+
+    fn is_react_component_call(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Call(call_expr) => {
+                if let Callee::Expr(callee) = &call_expr.callee {
+                    match &**callee {
+                        Expr::Ident(ident) => {
                             matches!(ident.sym.as_ref(), "memo" | "forwardRef")
                         }
                         Expr::Member(member) => {
@@ -564,214 +1838,879 @@ impl DefinitionVisitor {
     }
 
     fn has_ignore_comment(&self, span: Span) -> bool {
-        // Get the line number of the element
-        let start_pos = span.lo.0 as usize;
-        let lines: Vec<&str> = self.content.lines().collect();
-        let mut char_count = 0;
-        let mut target_line = 0;
-
-        for (i, line) in lines.iter().enumerate() {
-            let line_length = line.len() + 1; // +1 for newline
-            if char_count + line_length > start_pos {
-                target_line = i;
-                break;
-            }
-            char_count += line_length;
-        }
+        let line = self.line_index.position(span.lo).line;
 
         // Check the previous line for ignore comment
-        if target_line > 0 {
-            let prev_line = lines[target_line - 1].trim();
-            if prev_line == "// @ts-unused-ignore" {
+        if let Some(prev_line) = line.checked_sub(1).and_then(|prev| self.line_index.line(prev)) {
+            if prev_line.trim() == "// @ts-unused-ignore" {
                 return true;
             }
         }
 
         // Also check if there's an inline comment on the same line
-        if target_line < lines.len() {
-            let current_line = lines[target_line];
-            if current_line.contains("// @ts-unused-ignore") {
-                return true;
-            }
-        }
-
-        false
+        self.line_index
+            .line(line)
+            .is_some_and(|current_line| current_line.contains("// @ts-unused-ignore"))
     }
 }
 
+/// 名前空間オブジェクト全体への参照を表すエクスポート名
+const NAMESPACE_EXPORT: &str = "*";
+
+/// importで導入されたローカル束縛
+#[derive(Debug, Clone)]
+struct ImportBinding {
+    /// import元の指定子
+    source: String,
+    /// import元でのエクスポート名（`import * as ns` の場合は `*`）
+    imported: String,
+}
+
+/// 識別子が指す束縛
+enum BindingRef<'a> {
+    /// import束縛（`import()` / `require()` で得たモジュールオブジェクトを含む）
+    Import(&'a ImportBinding),
+    /// 関数・ブロック・クラス式・namespace 内で宣言されたローカル変数や引数
+    Scoped,
+    /// ファイルのトップレベルの宣言、またはグローバル
+    TopLevel,
+}
+
 /// 参照を収集するVisitor
 struct ReferenceVisitor {
     file: String,
     references: Vec<ElementReference>,
+    /// プロパティアクセス（`collect_properties` が有効な場合のみ）
+    properties: Vec<ElementReference>,
+    collect_properties: bool,
+    reexports: Vec<ReExport>,
+    dependencies: Vec<(String, String)>,
+    /// 束縛 → import束縛（`import()` / `require()` で得たモジュールオブジェクトの束縛を含む）
+    imports: HashMap<Id, ImportBinding>,
+    scopes: Scopes,
+    line_index: LineIndex,
 }
 
 impl ReferenceVisitor {
-    fn new(file: String, _content: &str) -> Self {
+    fn new(file: String, content: &str, scopes: Scopes, collect_properties: bool) -> Self {
         Self {
             file,
+            line_index: LineIndex::new(content),
             references: Vec::new(),
+            properties: Vec::new(),
+            collect_properties,
+            reexports: Vec::new(),
+            dependencies: Vec::new(),
+            imports: HashMap::new(),
+            scopes,
         }
     }
 
-    fn visit_module(&mut self, module: &Module) {
-        for item in &module.body {
-            self.visit_module_item(item);
+    /// 識別子の参照を記録（import束縛ならimport元へ帰属させる）
+    ///
+    /// 関数・ブロック内の束縛に解決される識別子は、同名のエクスポートと無関係なので記録しない
+    fn record_ident(&mut self, ident: &Ident, context: &str) {
+        match self.lookup(ident) {
+            // 名前空間オブジェクトが値として使われた（メンバーを特定できない）
+            BindingRef::Import(binding) if binding.imported == NAMESPACE_EXPORT => {
+                let source = binding.source.clone();
+                self.push_reference(NAMESPACE_EXPORT.to_string(), Some(source), "namespace", ident.span);
+            }
+            BindingRef::Import(binding) => {
+                let (name, source) = (binding.imported.clone(), binding.source.clone());
+                self.push_reference(name, Some(source), context, ident.span);
+            }
+            BindingRef::Scoped => {}
+            BindingRef::TopLevel => {
+                self.push_reference(ident.sym.to_string(), None, context, ident.span)
+            }
         }
     }
 
-    fn visit_module_item(&mut self, item: &ModuleItem) {
-        match item {
-            ModuleItem::ModuleDecl(decl) => self.visit_module_decl(decl),
-            ModuleItem::Stmt(stmt) => self.visit_stmt(stmt),
+    /// 識別子が指す束縛を resolver の解決結果（`SyntaxContext`）から求める
+    fn lookup(&self, ident: &Ident) -> BindingRef<'_> {
+        match self.imports.get(&ident.to_id()) {
+            Some(binding) => BindingRef::Import(binding),
+            None if self.scopes.is_scoped(ident) => BindingRef::Scoped,
+            None => BindingRef::TopLevel,
         }
     }
 
-    fn visit_module_decl(&mut self, decl: &ModuleDecl) {
-        match decl {
-            ModuleDecl::Import(import_decl) => {
+    fn push_reexport(&mut self, source: String, kind: ReExportKind, span: Span) {
+        self.reexports.push(ReExport {
+            file: self.file.clone(),
+            source,
+            kind,
+            position: self.line_index.position(span.lo),
+        });
+    }
+
+    fn push_reference(&mut self, name: String, source: Option<String>, context: &str, span: Span) {
+        self.references.push(ElementReference {
+            name,
+            file: self.file.clone(),
+            source,
+            position: self.line_index.position(span.lo),
+            offset: span.lo,
+            context: context.to_string(),
+        });
+    }
+
+    /// 式がモジュールオブジェクト（`import * as ns` の束縛、`import()`、`require()`）であれば、そのimport元を返す
+    fn namespace_source(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Ident(ident) => self.namespace_source_of(ident),
+            other => module_request(other).map(|(source, _)| source),
+        }
+    }
+
+    fn namespace_source_of(&self, ident: &Ident) -> Option<String> {
+        match self.lookup(ident) {
+            BindingRef::Import(binding) if binding.imported == NAMESPACE_EXPORT => {
+                Some(binding.source.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn push_property_reference(&mut self, name: &str, span: Span) {
+        if !self.collect_properties {
+            return;
+        }
+        self.properties.push(ElementReference {
+            name: name.to_string(),
+            file: self.file.clone(),
+            source: None,
+            position: self.line_index.position(span.lo),
+            offset: span.lo,
+            context: "property".to_string(),
+        });
+    }
+
+    /// `Status.Active` の `Status` 部分を (エクスポート名, import元) に解決する
+    ///
+    /// ローカル束縛ならimport元は None。名前空間オブジェクトそのものは対象外
+    fn member_owner(&self, obj: &Expr) -> Option<(String, Option<String>)> {
+        match obj {
+            Expr::Ident(ident) => self.owner_of_ident(ident),
+            // `ns.Status.Active`
+            Expr::Member(member) => {
+                let source = self.namespace_source(&member.obj)?;
+                Some((static_member_name(&member.prop)?, Some(source)))
+            }
+            _ => None,
+        }
+    }
+
+    /// 型位置の `Status.Active` / `ns.Status.Active` について `member_owner` と同様に解決する
+    fn entity_owner(&self, name: &TsEntityName) -> Option<(String, Option<String>)> {
+        match name {
+            TsEntityName::Ident(ident) => self.owner_of_ident(ident),
+            TsEntityName::TsQualifiedName(qualified) => match &qualified.left {
+                TsEntityName::Ident(left) => {
+                    let source = self.namespace_source_of(left)?;
+                    Some((qualified.right.sym.to_string(), Some(source)))
+                }
+                TsEntityName::TsQualifiedName(_) => None,
+            },
+        }
+    }
+
+    fn owner_of_ident(&self, ident: &Ident) -> Option<(String, Option<String>)> {
+        match self.lookup(ident) {
+            BindingRef::Import(binding) if binding.imported == NAMESPACE_EXPORT => None,
+            BindingRef::Import(binding) => {
+                Some((binding.imported.clone(), Some(binding.source.clone())))
+            }
+            BindingRef::Scoped => None,
+            BindingRef::TopLevel => Some((ident.sym.to_string(), None)),
+        }
+    }
+
+    /// `Owner.member` 形式の参照を、`Owner` 自体の参照とあわせて記録する
+    fn push_member_reference(
+        &mut self,
+        (owner, source): (String, Option<String>),
+        member: &str,
+        context: &str,
+        span: Span,
+    ) {
+        self.push_reference(format!("{}.{}", owner, member), source.clone(), context, span);
+        self.push_reference(owner, source, context, span);
+    }
+
+    /// `.then(callback)` のコールバックを、第1引数をモジュールオブジェクトとして走査する
+    fn visit_module_callback(&mut self, source: &str, callback: &Expr) {
+        let param = match callback {
+            Expr::Arrow(arrow) => arrow.params.first(),
+            Expr::Fn(func) => func.function.params.first().map(|param| &param.pat),
+            other => {
+                // コールバックが関数リテラルでない場合はモジュール全体を渡している
+                self.push_reference(
+                    NAMESPACE_EXPORT.to_string(),
+                    Some(source.to_string()),
+                    "dynamic import",
+                    other.span(),
+                );
+                other.visit_with(self);
+                return;
+            }
+        };
+
+        match param {
+            Some(Pat::Ident(ident)) => {
+                let binding = ImportBinding {
+                    source: source.to_string(),
+                    imported: NAMESPACE_EXPORT.to_string(),
+                };
+                self.imports.insert(ident.id.to_id(), binding);
+            }
+            Some(Pat::Object(object)) => {
+                let keys = object_pattern_keys(object)
+                    .unwrap_or_else(|| vec![NAMESPACE_EXPORT.to_string()]);
+                for key in keys {
+                    self.push_reference(key, Some(source.to_string()), "dynamic import", object.span);
+                }
+            }
+            _ => {}
+        }
+
+        match callback {
+            Expr::Arrow(arrow) => arrow.body.visit_with(self),
+            Expr::Fn(func) => func.function.body.visit_with(self),
+            _ => {}
+        }
+    }
+
+    /// import宣言を束縛として登録する（参照より先に処理する必要がある）
+    fn collect_imports(&mut self, module: &Module) {
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
+                let source = import_decl.src.value.to_string();
                 for specifier in &import_decl.specifiers {
-                    match specifier {
+                    let type_only = import_decl.type_only
+                        || matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only);
+                    let (local, imported) = match specifier {
                         ImportSpecifier::Named(named) => {
-                            let name = if let Some(imported) = &named.imported {
-                                match imported {
-                                    ModuleExportName::Ident(ident) => ident.sym.to_string(),
-                                    ModuleExportName::Str(str_lit) => str_lit.value.to_string(),
-                                }
-                            } else {
-                                named.local.sym.to_string()
+                            let imported = match &named.imported {
+                                Some(name) => module_export_name(name),
+                                None => named.local.sym.to_string(),
                             };
-
-                            self.references.push(ElementReference {
-                                name,
-                                file: self.file.clone(),
-                                line: 1,
-                                context: "import".to_string(),
-                            });
+                            (named.local.to_id(), imported)
                         }
                         ImportSpecifier::Default(default) => {
-                            self.references.push(ElementReference {
-                                name: default.local.sym.to_string(),
-                                file: self.file.clone(),
-                                line: 1,
-                                context: "import".to_string(),
-                            });
+                            (default.local.to_id(), "default".to_string())
                         }
                         ImportSpecifier::Namespace(namespace) => {
-                            self.references.push(ElementReference {
-                                name: namespace.local.sym.to_string(),
-                                file: self.file.clone(),
-                                line: 1,
-                                context: "import".to_string(),
-                            });
+                            // メンバーへのアクセスを個別に記録する
+                            self.imports.insert(
+                                namespace.local.to_id(),
+                                ImportBinding {
+                                    source: source.clone(),
+                                    imported: NAMESPACE_EXPORT.to_string(),
+                                },
+                            );
+                            continue;
                         }
-                    }
+                    };
+
+                    self.references.push(ElementReference {
+                        name: imported.clone(),
+                        file: self.file.clone(),
+                        source: Some(source.clone()),
+                        position: self.line_index.position(specifier.span().lo),
+                        offset: specifier.span().lo,
+                        context: if type_only { "import type" } else { "import" }.to_string(),
+                    });
+                    self.imports.insert(
+                        local,
+                        ImportBinding {
+                            source: source.clone(),
+                            imported,
+                        },
+                    );
                 }
             }
-            _ => {}
         }
     }
+}
+
+impl Visit for ReferenceVisitor {
+    fn visit_module(&mut self, module: &Module) {
+        self.collect_imports(module);
+        module.visit_children_with(self);
+
+        // 依存モジュール（副作用importを含む）
+        let mut sources: Vec<&str> = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                    Some(import_decl.src.value.as_ref())
+                }
+                _ => None,
+            })
+            .chain(self.reexports.iter().map(|r| r.source.as_str()))
+            .chain(self.references.iter().filter_map(|r| r.source.as_deref()))
+            .collect();
+        sources.sort();
+        sources.dedup();
+        self.dependencies = sources
+            .into_iter()
+            .map(|source| (self.file.clone(), source.to_string()))
+            .collect();
+
+        // 再エクスポートするだけのimportは使用とみなさない（バレル経由で判定する）
+        let forwarded: HashSet<(&str, &str)> = self
+            .reexports
+            .iter()
+            .filter_map(|r| match &r.kind {
+                ReExportKind::Named { imported, .. } => Some((r.source.as_str(), imported.as_str())),
+                _ => None,
+            })
+            .collect();
+        if !forwarded.is_empty() {
+            self.references.retain(|r| {
+                !(r.context.starts_with("import")
+                    && forwarded.contains(&(r.source.as_deref().unwrap_or_default(), r.name.as_str())))
+            });
+        }
+    }
+
+    fn visit_import_decl(&mut self, _import_decl: &ImportDecl) {
+        // collect_imports で処理済み
+    }
 
-    fn visit_stmt(&mut self, stmt: &Stmt) {
-        // 各種文の処理
-        match stmt {
-            Stmt::Expr(expr_stmt) => {
-                self.visit_expr(&expr_stmt.expr);
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Ident(ident) = expr {
+            self.record_ident(ident, "value");
+        }
+        expr.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        // `x.foo` はプロパティ `foo` へのアクセス（クラスメンバーの判定に使う）
+        // `this.foo` はクラス内部からのアクセスなので数えない
+        if !matches!(&*member.obj, Expr::This(_)) {
+            if let Some(name) = static_member_name(&member.prop) {
+                self.push_property_reference(&name, member.prop.span());
+            }
+        }
+
+        // `ns.foo` / `ns['foo']` / `require('./x').foo` はモジュールのメンバー `foo` への参照
+        if let Some(source) = self.namespace_source(&member.obj) {
+            if let Some(name) = static_member_name(&member.prop) {
+                // `import('./x').catch(...)` などはエクスポートを参照しない
+                let is_promise_method = module_request(&member.obj).is_some()
+                    && matches!(name.as_str(), "then" | "catch" | "finally");
+                if !is_promise_method {
+                    self.push_reference(name, Some(source), "value", member.prop.span());
+                }
+                return;
             }
-            Stmt::Decl(decl) => {
-                self.visit_decl(decl);
+        }
+
+        // `Status.Active` / `Status['Active']` はメンバーへの参照（enumメンバーの判定に使う）
+        if let Some(name) = static_member_name(&member.prop) {
+            if let Some(owner) = self.member_owner(&member.obj) {
+                self.push_member_reference(owner, &name, "member", member.span);
+                return;
             }
-            _ => {}
         }
+        member.visit_children_with(self);
     }
 
-    fn visit_expr(&mut self, expr: &Expr) {
-        match expr {
-            Expr::Ident(ident) => {
-                self.references.push(ElementReference {
-                    name: ident.sym.to_string(),
-                    file: self.file.clone(),
-                    line: 1,
-                    context: "usage".to_string(),
-                });
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // `React.lazy(() => import('./Page'))` は default エクスポートへの参照
+        if is_lazy_loader(&call.callee) {
+            if let Some(source) = call.args.first().and_then(|arg| lazy_import_source(&arg.expr)) {
+                self.push_reference("default".to_string(), Some(source), "dynamic import", call.span);
+                return;
             }
-            Expr::Call(call_expr) => {
-                self.visit_callee(&call_expr.callee);
-                for arg in &call_expr.args {
-                    self.visit_expr_or_spread(arg);
+        }
+
+        // `import('./x').then(m => m.Foo)` / `.then(({ Foo }) => ...)`
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Member(member) = &**callee {
+                let is_then = matches!(&member.prop, MemberProp::Ident(prop) if &*prop.sym == "then");
+                if let (true, Some((source, _))) = (is_then, module_request(&member.obj)) {
+                    if let Some(callback) = call.args.first() {
+                        self.visit_module_callback(&source, &callback.expr);
+                    }
+                    for arg in call.args.iter().skip(1) {
+                        arg.visit_with(self);
+                    }
+                    return;
                 }
             }
-            Expr::JSXElement(jsx_elem) => {
-                self.visit_jsx_element(jsx_elem);
+        }
+
+        // 単独の `import('./x')` / `require('./x')` はモジュール全体の使用とみなす
+        if let Some((source, context)) = module_request_call(call) {
+            self.push_reference(NAMESPACE_EXPORT.to_string(), Some(source), context, call.span);
+            return;
+        }
+
+        call.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        // `const mod = await import('./x')` / `const mod = require('./x')` は名前空間として扱う
+        if let (Pat::Ident(binding), Some(init)) = (&declarator.name, &declarator.init) {
+            if let Some((source, _)) = module_request(init) {
+                self.imports.insert(
+                    binding.id.to_id(),
+                    ImportBinding {
+                        source,
+                        imported: NAMESPACE_EXPORT.to_string(),
+                    },
+                );
+                return;
+            }
+        }
+
+        // `const { foo, bar: baz } = ns` は名前空間のメンバーへの参照
+        if let (Pat::Object(pattern), Some(init)) = (&declarator.name, &declarator.init) {
+            if let Some(source) = self.namespace_source(init) {
+                if let Some(keys) = object_pattern_keys(pattern) {
+                    for key in keys {
+                        self.push_reference(key, Some(source.clone()), "value", pattern.span);
+                    }
+                    declarator.name.visit_with(self);
+                    return;
+                }
             }
-            _ => {
-                // 他の式の処理は必要に応じて実装
+        }
+
+        // `const { fetch } = service` はプロパティへのアクセス
+        if let Pat::Object(pattern) = &declarator.name {
+            for key in object_pattern_keys(pattern).into_iter().flatten() {
+                self.push_property_reference(&key, pattern.span);
             }
         }
+        declarator.visit_children_with(self);
     }
 
-    fn visit_expr_or_spread(&mut self, expr: &ExprOrSpread) {
-        self.visit_expr(&expr.expr);
+    fn visit_prop(&mut self, prop: &Prop) {
+        // `{ foo }` の短縮プロパティは値としての参照
+        if let Prop::Shorthand(ident) = prop {
+            self.record_ident(ident, "value");
+        }
+        prop.visit_children_with(self);
     }
 
-    fn visit_callee(&mut self, callee: &Callee) {
-        match callee {
-            Callee::Expr(expr) => {
-                self.visit_expr(expr);
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        for specifier in &export.specifiers {
+            match (specifier, &export.src) {
+                // `export { a as b } from './mod'`
+                (ExportSpecifier::Named(named), Some(src)) => {
+                    let imported = module_export_name(&named.orig);
+                    let exported = named
+                        .exported
+                        .as_ref()
+                        .map(module_export_name)
+                        .unwrap_or_else(|| imported.clone());
+                    self.push_reexport(
+                        src.value.to_string(),
+                        ReExportKind::Named { imported, exported },
+                        named.span,
+                    );
+                }
+                // `export * as ns from './mod'`
+                (ExportSpecifier::Namespace(namespace), Some(src)) => {
+                    self.push_reexport(
+                        src.value.to_string(),
+                        ReExportKind::Namespace {
+                            exported: module_export_name(&namespace.name),
+                        },
+                        namespace.span,
+                    );
+                }
+                (ExportSpecifier::Named(named), None) => {
+                    let local = module_export_name(&named.orig);
+                    let exported = named
+                        .exported
+                        .as_ref()
+                        .map(module_export_name)
+                        .unwrap_or_else(|| local.clone());
+                    let binding = match &named.orig {
+                        ModuleExportName::Ident(ident) => self.imports.get(&ident.to_id()).cloned(),
+                        ModuleExportName::Str(_) => None,
+                    };
+                    match (binding, &named.orig) {
+                        // `import * as ns from './mod'; export { ns }`
+                        (Some(binding), _) if binding.imported == NAMESPACE_EXPORT => self.push_reexport(
+                            binding.source,
+                            ReExportKind::Namespace { exported },
+                            named.span,
+                        ),
+                        // `import { a } from './mod'; export { a }` は再エクスポート
+                        (Some(binding), _) => self.push_reexport(
+                            binding.source,
+                            ReExportKind::Named {
+                                imported: binding.imported,
+                                exported,
+                            },
+                            named.span,
+                        ),
+                        // `export { foo }` はローカル束縛の参照（`export { foo as default }` は
+                        // default export からの使用としてファイル内の使用に数える）
+                        (None, ModuleExportName::Ident(ident)) => {
+                            let context = if exported == "default" { "export default" } else { "export" };
+                            self.record_ident(ident, context)
+                        }
+                        (None, ModuleExportName::Str(_)) => {}
+                    }
+                }
+                (ExportSpecifier::Default(_), _) | (ExportSpecifier::Namespace(_), None) => {}
             }
-            _ => {}
         }
     }
 
-    fn visit_jsx_element(&mut self, jsx_elem: &JSXElement) {
-        if let JSXElementName::Ident(ident) = &jsx_elem.opening.name {
+    fn visit_export_default_expr(&mut self, export: &ExportDefaultExpr) {
+        let Expr::Ident(ident) = &*export.expr else {
+            export.visit_children_with(self);
+            return;
+        };
+        let binding = match self.lookup(ident) {
+            BindingRef::Import(binding) => Some(binding.clone()),
+            _ => None,
+        };
+        match binding {
+            // `import * as ns from './mod'; export default ns`
+            Some(binding) if binding.imported == NAMESPACE_EXPORT => self.push_reexport(
+                binding.source,
+                ReExportKind::Namespace {
+                    exported: "default".to_string(),
+                },
+                export.span,
+            ),
+            // `import Foo from './Foo'; export default Foo` は再エクスポート
+            Some(binding) => self.push_reexport(
+                binding.source,
+                ReExportKind::Named {
+                    imported: binding.imported,
+                    exported: "default".to_string(),
+                },
+                export.span,
+            ),
+            // `export default Foo` はローカル束縛の参照
+            _ => self.record_ident(ident, "export default"),
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.push_reexport(export.src.value.to_string(), ReExportKind::All, export.span);
+    }
+
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        match name {
+            JSXElementName::Ident(ident) => self.record_ident(ident, "jsx"),
+            JSXElementName::JSXMemberExpr(member) => self.visit_jsx_member_expr(member),
+            JSXElementName::JSXNamespacedName(_) => {}
+        }
+    }
+
+    fn visit_ts_entity_name(&mut self, name: &TsEntityName) {
+        // `ns.Foo` は名前空間のメンバー `Foo` への参照
+        if let TsEntityName::TsQualifiedName(qualified) = name {
+            if let TsEntityName::Ident(left) = &qualified.left {
+                if let Some(source) = self.namespace_source_of(left) {
+                    self.push_reference(
+                        qualified.right.sym.to_string(),
+                        Some(source),
+                        "type",
+                        qualified.right.span,
+                    );
+                    return;
+                }
+            }
+            // `Status.Active` / `ns.Status.Active` はメンバーへの参照
+            if let Some(owner) = self.entity_owner(&qualified.left) {
+                self.push_member_reference(owner, &qualified.right.sym, "type", qualified.span());
+                return;
+            }
+        }
+        // `Foo`, `typeof Foo`, `Ns.Foo` の型位置での参照
+        self.record_ident(leftmost_entity_ident(name), "type");
+    }
+
+    fn visit_ts_type_query(&mut self, query: &TsTypeQuery) {
+        // `typeof Status` / `typeof ns.Status` はenumオブジェクト全体の型
+        // （`keyof typeof Status` で全メンバーを列挙できる）
+        let namespace_member = match &query.expr_name {
+            TsTypeQueryExpr::TsEntityName(TsEntityName::TsQualifiedName(qualified)) => {
+                match &qualified.left {
+                    TsEntityName::Ident(left) => self
+                        .namespace_source_of(left)
+                        .map(|source| (&qualified.right, source)),
+                    TsEntityName::TsQualifiedName(_) => None,
+                }
+            }
+            _ => None,
+        };
+        match (&query.expr_name, namespace_member) {
+            (_, Some((member, source))) => {
+                self.push_reference(member.sym.to_string(), Some(source), "typeof", member.span)
+            }
+            (TsTypeQueryExpr::TsEntityName(TsEntityName::Ident(ident)), None) => {
+                self.record_ident(ident, "typeof")
+            }
+            (TsTypeQueryExpr::TsEntityName(name), None) => self.visit_ts_entity_name(name),
+            (TsTypeQueryExpr::Import(import_type), None) => self.visit_ts_import_type(import_type),
+        }
+        query.type_args.visit_with(self);
+    }
+
+    fn visit_ts_expr_with_type_args(&mut self, expr: &TsExprWithTypeArgs) {
+        // `interface A extends B` / `class A implements B`
+        if let Some(ident) = leftmost_expr_ident(&expr.expr) {
+            self.record_ident(ident, "type");
+        }
+        expr.type_args.visit_with(self);
+    }
+
+    fn visit_ts_import_type(&mut self, import_type: &TsImportType) {
+        // `import('./x').Foo` はモジュール `./x` の `Foo` への参照
+        if let Some(qualifier) = &import_type.qualifier {
             self.references.push(ElementReference {
-                name: ident.sym.to_string(),
+                name: leftmost_entity_ident(qualifier).sym.to_string(),
                 file: self.file.clone(),
-                line: 1,
-                context: "jsx".to_string(),
+                source: Some(import_type.arg.value.to_string()),
+                position: self.line_index.position(qualifier.span().lo),
+                offset: qualifier.span().lo,
+                context: "type".to_string(),
             });
         }
+        import_type.type_args.visit_with(self);
+    }
 
-        for child in &jsx_elem.children {
-            if let JSXElementChild::JSXElement(child_elem) = child {
-                self.visit_jsx_element(child_elem);
+    fn visit_jsx_member_expr(&mut self, member: &JSXMemberExpr) {
+        // `<ns.Button />` は名前空間のメンバー `Button` への参照
+        if let JSXObject::Ident(ident) = &member.obj {
+            if let Some(source) = self.namespace_source_of(ident) {
+                self.push_reference(member.prop.sym.to_string(), Some(source), "jsx", member.prop.span);
+                return;
             }
         }
+        // `<Foo.Bar />` はオブジェクト `Foo` の参照
+        match &member.obj {
+            JSXObject::Ident(ident) => self.record_ident(ident, "jsx"),
+            JSXObject::JSXMemberExpr(inner) => self.visit_jsx_member_expr(inner),
+        }
+    }
+}
+
+/// CommonJS のエクスポート先を判定
+///
+/// `module.exports` なら `Some(None)`、`module.exports.foo` / `exports.foo` なら `Some(Some("foo"))`
+fn commonjs_export_target(member: &MemberExpr) -> Option<Option<String>> {
+    if is_module_exports(member) {
+        return Some(None);
     }
 
-    fn visit_decl(&mut self, _decl: &Decl) {
-        // Declの処理は必要に応じて実装
+    let is_exports_object = match &*member.obj {
+        Expr::Member(inner) => is_module_exports(inner),
+        Expr::Ident(obj) => &*obj.sym == "exports",
+        _ => false,
+    };
+    if is_exports_object {
+        return static_member_name(&member.prop).map(Some);
     }
 
+    None
 }
 
-/// 静的関数：ファイルをASTで解析して定義を抽出
-fn parse_file_for_definitions_static(
-    file: &str,
-    content: &str,
-    config: &Config,
-) -> Result<Vec<ElementDefinition>, DetectorError> {
-    let input = StringInput::new(content, BytePos(0), BytePos(content.len() as u32));
+/// `module.exports` かどうか
+fn is_module_exports(member: &MemberExpr) -> bool {
+    matches!(&*member.obj, Expr::Ident(obj) if &*obj.sym == "module")
+        && static_member_name(&member.prop).as_deref() == Some("exports")
+}
+
+/// プロパティ名を文字列として取得（計算プロパティは除く）
+fn prop_name(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(str_lit) => Some(str_lit.value.to_string()),
+        _ => None,
+    }
+}
+
+/// 文字列リテラル、または式を含まないテンプレートリテラルの値を取得
+fn static_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl
+            .quasis
+            .first()
+            .map(|quasi| quasi.cooked.as_ref().unwrap_or(&quasi.raw).to_string()),
+        _ => None,
+    }
+}
+
+/// `import('./x')` / `require('./x')`（`await` や括弧を含む）の指定子と種類を取得
+fn module_request(expr: &Expr) -> Option<(String, &'static str)> {
+    match expr {
+        Expr::Paren(paren) => module_request(&paren.expr),
+        Expr::Await(await_expr) => module_request(&await_expr.arg),
+        Expr::Call(call) => module_request_call(call),
+        _ => None,
+    }
+}
+
+fn module_request_call(call: &CallExpr) -> Option<(String, &'static str)> {
+    let context = match &call.callee {
+        Callee::Import(_) => "dynamic import",
+        Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if &*ident.sym == "require") => {
+            "require"
+        }
+        _ => return None,
+    };
+    let source = static_string(&call.args.first()?.expr)?;
+    Some((source, context))
+}
+
+/// `lazy` / `React.lazy` / `loadable` / `dynamic` の呼び出しかどうか
+fn is_lazy_loader(callee: &Callee) -> bool {
+    const LOADERS: &[&str] = &["lazy", "loadable", "dynamic"];
+    let Callee::Expr(callee) = callee else {
+        return false;
+    };
+    match &**callee {
+        Expr::Ident(ident) => LOADERS.contains(&&*ident.sym),
+        Expr::Member(member) => {
+            matches!(&member.prop, MemberProp::Ident(prop) if &*prop.sym == "lazy")
+        }
+        _ => false,
+    }
+}
+
+/// `() => import('./x')` 形式のローダーから指定子を取得
+fn lazy_import_source(expr: &Expr) -> Option<String> {
+    let returned = match expr {
+        Expr::Arrow(arrow) => match &*arrow.body {
+            BlockStmtOrExpr::Expr(body) => &**body,
+            BlockStmtOrExpr::BlockStmt(block) => returned_expr(block)?,
+        },
+        Expr::Fn(func) => returned_expr(func.function.body.as_ref()?)?,
+        _ => return None,
+    };
+    match module_request(returned)? {
+        (source, "dynamic import") => Some(source),
+        _ => None,
+    }
+}
+
+/// `{ return expr; }` だけのブロックから式を取得
+fn returned_expr(block: &BlockStmt) -> Option<&Expr> {
+    match block.stmts.as_slice() {
+        [Stmt::Return(ReturnStmt { arg: Some(arg), .. })] => Some(arg),
+        _ => None,
+    }
+}
+
+/// `.foo` / `['foo']` 形式の静的なメンバー名を取得
+fn static_member_name(prop: &MemberProp) -> Option<String> {
+    match prop {
+        MemberProp::Ident(ident) => Some(ident.sym.to_string()),
+        MemberProp::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+            _ => None,
+        },
+        MemberProp::PrivateName(_) => None,
+    }
+}
+
+/// オブジェクト分割代入のキーを取得（rest や動的キーがある場合は None）
+fn object_pattern_keys(pattern: &ObjectPat) -> Option<Vec<String>> {
+    pattern
+        .props
+        .iter()
+        .map(|prop| match prop {
+            ObjectPatProp::KeyValue(key_value) => match &key_value.key {
+                PropName::Ident(ident) => Some(ident.sym.to_string()),
+                PropName::Str(str_lit) => Some(str_lit.value.to_string()),
+                _ => None,
+            },
+            ObjectPatProp::Assign(assign) => Some(assign.key.id.sym.to_string()),
+            ObjectPatProp::Rest(_) => None,
+        })
+        .collect()
+}
+
+/// `ModuleExportName` を文字列として取得
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str_lit) => str_lit.value.to_string(),
+    }
+}
+
+/// `A.B.C` の先頭の識別子 `A` を取得
+fn leftmost_entity_ident(name: &TsEntityName) -> &Ident {
+    match name {
+        TsEntityName::Ident(ident) => ident,
+        TsEntityName::TsQualifiedName(qualified) => leftmost_entity_ident(&qualified.left),
+    }
+}
+
+/// `a.b.c` 形式の式の先頭の識別子 `a` を取得
+fn leftmost_expr_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Member(member) => leftmost_expr_ident(&member.obj),
+        _ => None,
+    }
+}
 
-    let lexer = Lexer::new(
+/// ファイルの拡張子に応じた構文設定を返す
+fn syntax_for(file: &str, config: &Config) -> Syntax {
+    let is_js = [".js", ".jsx", ".mjs", ".cjs"]
+        .iter()
+        .any(|ext| file.ends_with(ext));
+
+    if is_js {
+        Syntax::Es(EsConfig {
+            jsx: file.ends_with(".jsx") || (file.ends_with(".js") && config.jsx_in_js),
+            decorators: true,
+            ..Default::default()
+        })
+    } else {
         Syntax::Typescript(TsConfig {
             tsx: file.ends_with(".tsx"),
             decorators: true,
             dts: file.ends_with(".d.ts"),
             no_early_errors: true,
             disallow_ambiguous_jsx_like: false,
-        }),
-        Default::default(),
-        input,
-        None,
-    );
+        })
+    }
+}
+
+/// ファイルをモジュールとしてパースし、識別子を束縛ごとに解決する
+fn parse_module(file: &str, content: &str, config: &Config) -> Result<(Module, Scopes), DetectorError> {
+    let syntax = syntax_for(file, config);
+    let input = StringInput::new(content, BytePos(0), BytePos(content.len() as u32));
+    let lexer = Lexer::new(syntax, Default::default(), input, None);
 
     let mut parser = Parser::new_from(lexer);
-    let module = parser.parse_module()
+    let mut module = parser
+        .parse_module()
         .map_err(|e| DetectorError::ParseError(format!("Failed to parse {}: {:?}", file, e)))?;
+    let scopes = Scopes::resolve(&mut module, syntax.typescript());
+    Ok((module, scopes))
+}
 
-    let mut visitor = DefinitionVisitor::new(file.to_string(), config, content.to_string());
-    visitor.visit_module(&module);
+/// 静的関数：ファイルをASTで解析して定義を抽出
+fn parse_file_for_definitions_static(
+    file: &str,
+    content: &str,
+    config: &Config,
+    tsconfig: Option<&tsconfig::TsConfig>,
+) -> Result<Vec<ElementDefinition>, DetectorError> {
+    let (module, scopes) = parse_module(file, content, config)?;
+
+    let mut visitor = DefinitionVisitor::new(file.to_string(), config, content, tsconfig);
+    visitor.visit_module(&module, &scopes);
 
     Ok(visitor.definitions)
 }
@@ -780,36 +2719,30 @@ fn parse_file_for_definitions_static(
 fn parse_file_for_references_static(
     file: &str,
     content: &str,
-) -> Result<Vec<ElementReference>, DetectorError> {
-    let input = StringInput::new(content, BytePos(0), BytePos(content.len() as u32));
-
-    let lexer = Lexer::new(
-        Syntax::Typescript(TsConfig {
-            tsx: file.ends_with(".tsx"),
-            decorators: true,
-            dts: file.ends_with(".d.ts"),
-            no_early_errors: true,
-            disallow_ambiguous_jsx_like: false,
-        }),
-        Default::default(),
-        input,
-        None,
+    config: &Config,
+) -> Result<FileReferences, DetectorError> {
+    let (module, scopes) = parse_module(file, content, config)?;
+
+    let mut visitor = ReferenceVisitor::new(
+        file.to_string(),
+        content,
+        scopes,
+        config.detection_types.class_members,
     );
-
-    let mut parser = Parser::new_from(lexer);
-    let module = parser.parse_module()
-        .map_err(|e| DetectorError::ParseError(format!("Failed to parse {}: {:?}", file, e)))?;
-
-    let mut visitor = ReferenceVisitor::new(file.to_string(), content);
-    visitor.visit_module(&module);
-
-    Ok(visitor.references)
+    module.visit_with(&mut visitor);
+
+    Ok(FileReferences {
+        references: visitor.references,
+        properties: visitor.properties,
+        reexports: visitor.reexports,
+        dependencies: visitor.dependencies,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::DetectionTypes;
+    use crate::types::{default_entry_points, default_extensions, DetectionTypes};
 
     fn create_test_config() -> Config {
         Config {
@@ -818,11 +2751,25 @@ mod tests {
                 types: true,
                 interfaces: true,
                 functions: true,
+                hooks: true,
                 variables: true,
                 enums: true,
+                classes: true,
+                class_members: false,
+                locals: false,
+                imports: true,
+                files: true,
+                enum_members: false,
             },
             search_dirs: vec![".".to_string()],
             exclude_patterns: vec![],
+            extensions: default_extensions(),
+            jsx_in_js: true,
+            entry_points: default_entry_points(),
+            public_entry_points: vec![],
+            package_entry_points: vec![],
+            html_entry_files: vec![],
+            naming_filter: false,
             ci: None,
         }
     }
@@ -837,7 +2784,7 @@ export type UnusedType = string;
 
 export type UsedType = number;
 "#;
-        let result = parse_file_for_definitions_static("test.ts", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.ts", content, &config, None).unwrap();
 
         // Find UnusedType and UsedType
         let unused_type = result.iter().find(|def| def.name == "UnusedType").unwrap();
@@ -860,7 +2807,7 @@ export interface UsedInterface {
     prop: number;
 }
 "#;
-        let result = parse_file_for_definitions_static("test.ts", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.ts", content, &config, None).unwrap();
 
         let unused_interface = result.iter().find(|def| def.name == "UnusedInterface").unwrap();
         let used_interface = result.iter().find(|def| def.name == "UsedInterface").unwrap();
@@ -882,7 +2829,7 @@ export function usedFunction() {
     return "world";
 }
 "#;
-        let result = parse_file_for_definitions_static("test.ts", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.ts", content, &config, None).unwrap();
 
         let unused_function = result.iter().find(|def| def.name == "unusedFunction").unwrap();
         let used_function = result.iter().find(|def| def.name == "usedFunction").unwrap();
@@ -904,7 +2851,7 @@ export const UsedComponent = () => {
     return <div>Used</div>;
 };
 "#;
-        let result = parse_file_for_definitions_static("test.tsx", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.tsx", content, &config, None).unwrap();
 
         let unused_component = result.iter().find(|def| def.name == "UnusedComponent").unwrap();
         let used_component = result.iter().find(|def| def.name == "UsedComponent").unwrap();
@@ -928,7 +2875,7 @@ export enum UsedEnum {
     VALUE4 = "value4"
 }
 "#;
-        let result = parse_file_for_definitions_static("test.ts", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.ts", content, &config, None).unwrap();
 
         let unused_enum = result.iter().find(|def| def.name == "UnusedEnum").unwrap();
         let used_enum = result.iter().find(|def| def.name == "UsedEnum").unwrap();
@@ -944,7 +2891,7 @@ export enum UsedEnum {
         let content = r#"export type UnusedType = string; // @ts-unused-ignore
 export type UsedType = number;
 "#;
-        let result = parse_file_for_definitions_static("test.ts", content, &config).unwrap();
+        let result = parse_file_for_definitions_static("test.ts", content, &config, None).unwrap();
 
         let unused_type = result.iter().find(|def| def.name == "UnusedType").unwrap();
         let used_type = result.iter().find(|def| def.name == "UsedType").unwrap();
@@ -952,4 +2899,1275 @@ export type UsedType = number;
         assert!(unused_type.should_ignore, "UnusedType should be marked for ignore with inline comment");
         assert!(!used_type.should_ignore, "UsedType should not be marked for ignore");
     }
-}
\ No newline at end of file
+
+    fn write_file(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn detect_in(root: &Path) -> DetectionResult {
+        detect_in_with(root, |_| {})
+    }
+
+    /// `root/src` を検索対象にしたテスト用設定を `tweak` で調整して検出する
+    fn detect_in_with(root: &Path, tweak: impl FnOnce(&mut Config)) -> DetectionResult {
+        let mut config = Config {
+            search_dirs: vec![root.join("src").to_string_lossy().to_string()],
+            ..create_test_config()
+        };
+        tweak(&mut config);
+        UnusedElementDetector::new(config).unwrap().detect().unwrap()
+    }
+
+    /// 未使用として報告された要素 `name` の種類
+    fn kind_of(result: &DetectionResult, name: &str) -> Option<ElementType> {
+        result
+            .unused
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| e.element_type.clone())
+    }
+
+    fn unused_in<'a>(result: &'a DetectionResult, name: &str) -> Vec<&'a str> {
+        result
+            .unused
+            .iter()
+            .filter(|e| e.name == name)
+            .flat_map(|e| e.definition_files.iter().map(|f| f.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_same_name_exports_are_matched_by_resolved_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/admin/Button.tsx", "export const Button = () => <button />;\n");
+        write_file(root, "src/shop/Button.tsx", "export const Button = () => <button />;\n");
+        write_file(
+            root,
+            "src/app.tsx",
+            "import { Button } from './shop/Button';\nexport const App = () => <Button />;\n",
+        );
+
+        let result = detect_in(root);
+        let unused_buttons = unused_in(&result, "Button");
+
+        assert_eq!(unused_buttons.len(), 1);
+        assert!(unused_buttons[0].ends_with("admin/Button.tsx"));
+    }
+
+    #[test]
+    fn test_index_file_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/utils/index.ts", "export const formatDate = () => '';\n");
+        write_file(
+            root,
+            "src/app.ts",
+            "import { formatDate } from './utils';\nformatDate();\n",
+        );
+
+        let result = detect_in(root);
+        assert!(unused_in(&result, "formatDate").is_empty());
+    }
+
+    #[test]
+    fn test_tsconfig_path_alias_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] } } }"#,
+        );
+        write_file(root, "src/components/Button.tsx", "export const Button = () => <button />;\n");
+        write_file(root, "src/legacy/Button.tsx", "export const Button = () => <button />;\n");
+        write_file(
+            root,
+            "src/pages/home.tsx",
+            "import { Button } from '@/components/Button';\nexport const Home = () => <Button />;\n",
+        );
+
+        let result = detect_in(root);
+        let unused_buttons = unused_in(&result, "Button");
+
+        assert_eq!(unused_buttons.len(), 1);
+        assert!(unused_buttons[0].ends_with("legacy/Button.tsx"));
+    }
+
+    #[test]
+    fn test_references_collected_from_nested_scopes() {
+        let content = r#"import { formatDate, Icon, THEME, createStore } from './lib';
+
+export function Page({ when = formatDate() }) {
+    const handlers = {
+        onClick: () => {
+            if (true) {
+                return createStore();
+            }
+        },
+    };
+    return <div style={{ color: THEME }}>{[1].map(() => <Icon key="a" />)}</div>;
+}
+
+class Widget {
+    render() {
+        return helper;
+    }
+}
+"#;
+        let refs = parse_file_for_references_static("page.tsx", content, &create_test_config())
+            .unwrap()
+            .references;
+        let usage_of = |name: &str| {
+            refs.iter()
+                .filter(|r| r.name == name && r.context != "import")
+                .collect::<Vec<_>>()
+        };
+
+        for name in ["formatDate", "Icon", "THEME", "createStore"] {
+            let usages = usage_of(name);
+            assert!(!usages.is_empty(), "{} should be referenced", name);
+            assert!(usages.iter().all(|r| r.source.as_deref() == Some("./lib")));
+        }
+        assert_eq!(usage_of("helper")[0].source, None);
+    }
+
+    #[test]
+    fn test_type_position_references() {
+        let content = r#"import type { BaseProps, User, Schema, Shape, Config, Api } from './types';
+import { useState } from 'react';
+
+export interface Props extends BaseProps {}
+
+export class Service implements Api {}
+
+export function useUser() {
+    const [user] = useState<User>();
+    const value = {} satisfies Config;
+    const parsed = {} as Shape;
+    type Key = Schema['key'];
+    type Query = typeof parsed;
+    let remote: import('./remote').RemoteUser;
+    return user;
+}
+"#;
+        let refs = parse_file_for_references_static("user.ts", content, &create_test_config())
+            .unwrap()
+            .references;
+        let contexts_of = |name: &str| {
+            refs.iter()
+                .filter(|r| r.name == name && !r.context.starts_with("import"))
+                .map(|r| (r.context.as_str(), r.source.as_deref()))
+                .collect::<Vec<_>>()
+        };
+
+        for name in ["BaseProps", "Api", "User", "Config", "Shape", "Schema"] {
+            assert_eq!(contexts_of(name), vec![("type", Some("./types"))], "{}", name);
+        }
+        assert_eq!(contexts_of("useState"), vec![("value", Some("react"))]);
+        // 関数内のローカル変数への参照は記録しない
+        assert!(contexts_of("parsed").is_empty());
+        assert_eq!(contexts_of("RemoteUser"), vec![("type", Some("./remote"))]);
+
+        let user_import = refs
+            .iter()
+            .find(|r| r.name == "User" && r.context.starts_with("import"))
+            .unwrap();
+        assert_eq!(user_import.context, "import type");
+    }
+
+    #[test]
+    fn test_reexports_through_barrels() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/components/Button.tsx", "export const Button = () => <button />;\n");
+        write_file(root, "src/components/Card.tsx", "export const Card = () => <div />;\n");
+        write_file(root, "src/components/Modal.tsx", "export const Modal = () => <div />;\n");
+        write_file(root, "src/utils/math.ts", "export const addNumbers = () => 1;\nexport const subNumbers = () => 1;\n");
+        write_file(root, "src/utils/strings.ts", "export const upper = () => 1;\nexport const lower = () => 1;\n");
+        write_file(
+            root,
+            "src/utils/index.ts",
+            "export * as math from './math';\nexport * as strings from './strings';\n",
+        );
+        write_file(
+            root,
+            "src/components/index.ts",
+            "export { Button as PrimaryButton } from './Button';\nexport * from './Card';\nimport { Modal } from './Modal';\nexport { Modal };\n",
+        );
+        write_file(root, "src/index.ts", "export * from './components';\n");
+        write_file(
+            root,
+            "src/main.tsx",
+            "import { PrimaryButton, Card } from '.';\nimport { math, strings } from './utils';\nexport const App = () => <Card><PrimaryButton /></Card>;\nmath.addNumbers();\nconsole.log(strings);\n",
+        );
+
+        let result = detect_in(root);
+
+        assert!(unused_in(&result, "Button").is_empty());
+        assert!(unused_in(&result, "Card").is_empty());
+        assert!(unused_in(&result, "addNumbers").is_empty());
+        // `export * as math` 経由でもメンバーアクセスで照合する
+        assert_eq!(unused_in(&result, "subNumbers").len(), 1);
+        // 名前空間オブジェクトが値として使われたら全メンバーを使用扱いにし、その旨を報告する
+        assert!(unused_in(&result, "upper").is_empty());
+        assert!(unused_in(&result, "lower").is_empty());
+        assert!(result.notes.iter().any(|n| {
+            n.contains("namespace re-export of") && n.ends_with("all its exports are treated as used")
+        }));
+
+        // 再エクスポートされているだけの要素は未使用として報告される
+        let modal = result.unused.iter().find(|e| e.name == "Modal").unwrap();
+        assert_eq!(modal.reexported_from.len(), 2);
+        assert!(modal.reexported_from.iter().any(|f| f.ends_with("components/index.ts")));
+        assert!(modal.reexported_from.iter().any(|f| f.ends_with("src/index.ts")));
+    }
+
+    #[test]
+    fn test_namespace_import_member_tracking() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/api.ts",
+            "export const fetchUser = () => 1;\nexport const fetchPosts = () => 1;\nexport const deleteUser = () => 1;\nexport const unusedCall = () => 1;\nexport type User = { id: string };\n",
+        );
+        write_file(
+            root,
+            "src/app.ts",
+            "import * as api from './api';\nconst { deleteUser } = api;\nexport const load = (): api.User => api.fetchUser() && api['fetchPosts']() && deleteUser();\n",
+        );
+        write_file(root, "src/tools.ts", "export const toolA = () => 1;\n");
+        write_file(
+            root,
+            "src/registry.ts",
+            "import * as tools from './tools';\nexport const register = (fn: unknown) => fn;\nregister(tools);\n",
+        );
+
+        let result = detect_in(root);
+
+        for name in ["fetchUser", "fetchPosts", "deleteUser", "User"] {
+            assert!(unused_in(&result, name).is_empty(), "{} should be used", name);
+        }
+        assert_eq!(unused_in(&result, "unusedCall").len(), 1);
+
+        // 値として渡された名前空間は全エクスポートを使用扱いにし、その旨を報告する
+        assert!(unused_in(&result, "toolA").is_empty());
+        assert_eq!(result.notes.len(), 1);
+        assert!(result.notes[0].contains("'./tools'"));
+    }
+
+    #[test]
+    fn test_dynamic_import_and_require_references() {
+        let content = r#"import React, { lazy } from 'react';
+
+const Settings = React.lazy(() => import('./pages/Settings'));
+const Profile = lazy(() => import(`./pages/Profile`).then(m => ({ default: m.ProfilePage })));
+
+export async function load() {
+    const { formatDate } = await import('./utils/date');
+    const charts = await import('./charts');
+    charts.renderChart();
+    import('./analytics').then(({ track }) => track());
+    const helper = require('./helper').helper;
+    const { parse } = require('./parser');
+    import('./side-effect');
+    return [Settings, Profile, formatDate, helper, parse];
+}
+"#;
+        let refs = parse_file_for_references_static("app.tsx", content, &create_test_config())
+            .unwrap()
+            .references;
+        let has = |source: &str, name: &str| {
+            refs.iter()
+                .any(|r| r.source.as_deref() == Some(source) && r.name == name)
+        };
+
+        assert!(has("./pages/Settings", "default"));
+        assert!(has("./pages/Profile", "ProfilePage"));
+        assert!(has("./utils/date", "formatDate"));
+        assert!(has("./charts", "renderChart"));
+        assert!(has("./analytics", "track"));
+        assert!(has("./helper", "helper"));
+        assert!(has("./parser", "parse"));
+        assert!(has("./side-effect", NAMESPACE_EXPORT));
+        assert!(!has("./pages/Profile", "then"));
+    }
+
+    #[test]
+    fn test_dynamic_import_marks_exports_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/pages/Settings.tsx",
+            "export const SettingsPage = () => <div />;\nexport const SettingsHeader = () => <div />;\n",
+        );
+        write_file(
+            root,
+            "src/routes.tsx",
+            "import { lazy } from 'react';\nexport const Settings = lazy(() => import('./pages/Settings').then(m => ({ default: m.SettingsPage })));\n",
+        );
+
+        let result = detect_in(root);
+        assert!(unused_in(&result, "SettingsPage").is_empty());
+        assert_eq!(unused_in(&result, "SettingsHeader").len(), 1);
+    }
+
+    #[test]
+    fn test_javascript_sources_are_scanned() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/legacy/format.js",
+            "export const formatPrice = (n) => `$${n}`;\nexport const formatDate = (d) => d;\n",
+        );
+        write_file(root, "src/components/Badge.jsx", "export const Badge = () => <span />;\n");
+        write_file(root, "src/lib/math.mjs", "export const sum = (a, b) => a + b;\n");
+        write_file(
+            root,
+            "src/app.js",
+            "import { formatPrice } from './legacy/format';\nimport { Badge } from './components/Badge';\nexport const App = () => <Badge label={formatPrice(1)} />;\n",
+        );
+        write_file(
+            root,
+            "src/cart.ts",
+            "import { sum } from './lib/math.mjs';\nexport const total = sum(1, 2);\n",
+        );
+
+        let result = detect_in(root);
+        assert!(unused_in(&result, "formatPrice").is_empty());
+        assert!(unused_in(&result, "Badge").is_empty());
+        assert!(unused_in(&result, "sum").is_empty());
+        assert_eq!(unused_in(&result, "formatDate").len(), 1);
+    }
+
+    #[test]
+    fn test_syntax_for_extensions() {
+        let config = create_test_config();
+        assert!(matches!(syntax_for("a.tsx", &config), Syntax::Typescript(ts) if ts.tsx));
+        assert!(matches!(syntax_for("a.mts", &config), Syntax::Typescript(ts) if !ts.tsx));
+        assert!(matches!(syntax_for("a.jsx", &config), Syntax::Es(es) if es.jsx));
+        assert!(matches!(syntax_for("a.cjs", &config), Syntax::Es(es) if !es.jsx));
+
+        let no_jsx = Config {
+            jsx_in_js: false,
+            ..create_test_config()
+        };
+        assert!(matches!(syntax_for("a.js", &config), Syntax::Es(es) if es.jsx));
+        assert!(matches!(syntax_for("a.js", &no_jsx), Syntax::Es(es) if !es.jsx));
+    }
+
+    #[test]
+    fn test_commonjs_export_definitions() {
+        let config = create_test_config();
+        let content = r#"function formatName() {}
+const parseArgs = () => {};
+const MAX_RETRIES = 3;
+
+module.exports = { formatName, parse: parseArgs, MAX_RETRIES, validate() {} };
+module.exports.loadConfig = function () {};
+exports.saveConfig = (config) => config;
+exports.DEFAULT_PORT = 8080;
+"#;
+        let defs = parse_file_for_definitions_static("cli.cjs", content, &config, None).unwrap();
+        let kind_of = |name: &str| {
+            defs.iter()
+                .find(|d| d.name == name)
+                .map(|d| d.element_type.clone())
+        };
+
+        assert_eq!(kind_of("formatName"), Some(ElementType::Function));
+        assert_eq!(kind_of("parse"), Some(ElementType::Function));
+        assert_eq!(kind_of("MAX_RETRIES"), Some(ElementType::Variable));
+        assert_eq!(kind_of("validate"), Some(ElementType::Function));
+        assert_eq!(kind_of("loadConfig"), Some(ElementType::Function));
+        assert_eq!(kind_of("saveConfig"), Some(ElementType::Function));
+        assert_eq!(kind_of("DEFAULT_PORT"), Some(ElementType::Variable));
+    }
+
+    #[test]
+    fn test_commonjs_require_usages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/lib/strings.cjs",
+            "exports.slugify = (s) => s;\nexports.capitalize = (s) => s;\nexports.truncate = (s) => s;\n",
+        );
+        write_file(
+            root,
+            "src/index.cjs",
+            "const { slugify } = require('./lib/strings.cjs');\nconst cap = require('./lib/strings.cjs').capitalize;\nmodule.exports.run = () => cap(slugify('a'));\n",
+        );
+
+        let result = detect_in(root);
+        assert!(unused_in(&result, "slugify").is_empty());
+        assert!(unused_in(&result, "capitalize").is_empty());
+        assert_eq!(unused_in(&result, "truncate").len(), 1);
+    }
+
+    #[test]
+    fn test_unused_file_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/main.tsx", "import './polyfills';\nimport { App } from './App';\nApp();\n");
+        write_file(root, "src/polyfills.ts", "globalThis.x = 1;\n");
+        write_file(
+            root,
+            "src/App.tsx",
+            "import { lazy } from 'react';\nexport * from './types';\nexport const Page = lazy(() => import('./pages/Page'));\nexport const App = () => null;\n",
+        );
+        write_file(root, "src/types.ts", "export type Id = string;\n");
+        write_file(root, "src/pages/Page.tsx", "export default function Page() { return null; }\n");
+        write_file(root, "src/legacy/OldWidget.tsx", "export const OldWidget = () => null;\n");
+        write_file(root, "src/legacy/helpers.ts", "export const helper = () => 1;\n");
+        write_file(root, "vite.config.ts", "export default {};\n");
+
+        let result = detect_in(root);
+
+        assert_eq!(result.unused_files.len(), 2, "{:?}", result.unused_files);
+        assert!(result.unused_files[0].ends_with("legacy/OldWidget.tsx"));
+        assert!(result.unused_files[1].ends_with("legacy/helpers.ts"));
+    }
+
+    #[test]
+    fn test_reachability_from_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/main.ts", "import { run } from './app';\nrun();\n");
+        write_file(root, "src/app.ts", "import { slugify } from './utils';\nexport const run = () => slugify('a');\n");
+        write_file(
+            root,
+            "src/utils.ts",
+            "export const slugify = (s: string) => s;\nexport const formatDate = (d: Date) => d;\n",
+        );
+        // 互いに参照し合うだけのデッドモジュール
+        write_file(
+            root,
+            "src/old/report.ts",
+            "import { chart } from './chart';\nimport { formatDate } from '../utils';\nexport const report = () => chart(formatDate(new Date()));\n",
+        );
+        write_file(
+            root,
+            "src/old/chart.ts",
+            "import { report } from './report';\nexport const chart = (x: unknown) => x;\nexport const redraw = () => report();\n",
+        );
+
+        let result = detect_in(root);
+
+        assert!(unused_in(&result, "slugify").is_empty());
+        assert!(unused_in(&result, "run").is_empty());
+        assert_eq!(unused_in(&result, "formatDate").len(), 1);
+        assert_eq!(unused_in(&result, "chart").len(), 1);
+        assert_eq!(unused_in(&result, "report").len(), 1);
+        assert!(result
+            .notes
+            .iter()
+            .any(|n| n.contains("'formatDate' is only referenced from dead code")));
+        assert_eq!(result.unused_files.len(), 2, "{:?}", result.unused_files);
+        assert!(result.unused_files[0].ends_with("old/chart.ts"));
+        assert!(result.unused_files[1].ends_with("old/report.ts"));
+    }
+
+    #[test]
+    fn test_public_api_exports_are_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/lib.ts", "export * from './button';\nexport { useToggle } from './hooks';\n");
+        write_file(root, "src/button.tsx", "export const Button = () => <button />;\n");
+        write_file(
+            root,
+            "src/hooks.ts",
+            "export const useToggle = () => true;\nexport const useInternal = () => false;\n",
+        );
+
+        let package_json: serde_json::Value =
+            serde_json::from_str(r#"{ "exports": { ".": "./src/lib.ts" } }"#).unwrap();
+        let result = detect_in_with(root, |config| {
+            config.package_entry_points = crate::config::package_entry_points(root, &package_json);
+        });
+
+        assert!(unused_in(&result, "Button").is_empty());
+        assert!(unused_in(&result, "useToggle").is_empty());
+        assert_eq!(unused_in(&result, "useInternal").len(), 1);
+    }
+
+    #[test]
+    fn test_public_entry_points_are_reachability_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/main.ts", "console.log('app');
+");
+        write_file(root, "src/lib/index.ts", "export { pad } from './format';
+");
+        write_file(
+            root,
+            "src/lib/format.ts",
+            "import { helper } from './helper';
+export const pad = (s: string) => helper(s);
+",
+        );
+        write_file(root, "src/lib/helper.ts", "export const helper = (s: string) => s;
+");
+        write_file(root, "src/lib/legacy.ts", "export const legacy = () => 1;
+");
+
+        let result = detect_in_with(root, |config| {
+            config.entry_points = vec!["src/main.ts".to_string()];
+            config.public_entry_points = vec!["src/lib/index.ts".to_string()];
+        });
+
+        assert!(unused_in(&result, "pad").is_empty());
+        assert!(unused_in(&result, "helper").is_empty());
+        assert!(!result.notes.iter().any(|n| n.contains("dead code")), "{:?}", result.notes);
+        // 公開APIから到達できないファイルだけが未使用
+        assert_eq!(result.unused_files.len(), 1, "{:?}", result.unused_files);
+        assert!(result.unused_files[0].ends_with("src/lib/legacy.ts"));
+    }
+
+    #[test]
+    fn test_package_entry_points_do_not_match_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/app.ts", "import { helper } from './utils';\nhelper();\n");
+        write_file(
+            root,
+            "src/utils/index.ts",
+            "export const helper = () => 1;\nexport const deadHelper = () => 2;\n",
+        );
+        write_file(root, "src/components/index.ts", "export const Card = () => null;\n");
+
+        // ルートの package.json の `main` はパッケージ直下の index だけを指す
+        let package_json: serde_json::Value =
+            serde_json::from_str(r#"{ "main": "index.js" }"#).unwrap();
+        let result = detect_in_with(root, |config| {
+            config.entry_points = vec!["src/app.ts".to_string()];
+            config.package_entry_points =
+                crate::config::package_entry_points(Path::new(""), &package_json);
+        });
+
+        assert!(unused_in(&result, "helper").is_empty());
+        assert_eq!(unused_in(&result, "deadHelper").len(), 1);
+        assert_eq!(result.unused_files.len(), 1, "{:?}", result.unused_files);
+        assert!(result.unused_files[0].ends_with("src/components/index.ts"));
+    }
+
+    #[test]
+    fn test_html_script_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "index.html",
+            "<div id=\"root\"></div>\n<script type=\"module\" src=\"/src/boot.tsx\"></script>\n",
+        );
+        write_file(root, "src/boot.tsx", "import { App } from './App';\nApp();\n");
+        write_file(root, "src/App.tsx", "export const App = () => null;\n");
+        write_file(root, "src/Unused.tsx", "export const Unused = () => null;\n");
+
+        let result = detect_in_with(root, |config| {
+            config.html_entry_files = vec![root.join("index.html").to_string_lossy().to_string()];
+        });
+
+        assert!(unused_in(&result, "App").is_empty());
+        assert_eq!(result.unused_files.len(), 1, "{:?}", result.unused_files);
+        assert!(result.unused_files[0].ends_with("src/Unused.tsx"));
+    }
+
+    #[test]
+    fn test_enum_member_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/api/status.ts",
+            "export enum Status { Active, Inactive, Archived, Pending }\nexport enum Role { Admin = 'admin', User = 'user' }\nexport const isPending = (s: Status) => s === Status.Pending;\n",
+        );
+        write_file(
+            root,
+            "src/app.ts",
+            "import { Status, Role } from './api/status';\nimport * as api from './api/status';\nexport const a = Status.Active;\ntype Archived = { status: api.Status.Archived };\nexport const roles = Object.values(Role);\n",
+        );
+        write_file(
+            root,
+            "src/level.ts",
+            "export enum Level { Low, High }\nexport enum Mode { On, Off }\n",
+        );
+        write_file(
+            root,
+            "src/settings.ts",
+            "import { Level, Mode } from './level';\nexport type LevelKey = keyof typeof Level;\nexport const apply = (mode: Mode) => mode;\n",
+        );
+
+        let result = detect_in_with(root, |config| {
+            config.detection_types.enum_members = true;
+        });
+
+        assert!(unused_in(&result, "Status.Active").is_empty());
+        assert!(unused_in(&result, "Status.Archived").is_empty());
+        assert!(unused_in(&result, "Status.Pending").is_empty());
+        assert_eq!(unused_in(&result, "Status.Inactive").len(), 1);
+        // `Object.values(Role)` でenumオブジェクトが値として使われている
+        assert!(unused_in(&result, "Role.Admin").is_empty());
+        assert!(unused_in(&result, "Role.User").is_empty());
+        assert!(result
+            .notes
+            .iter()
+            .any(|n| n.contains("enum 'Role' is used as a value")));
+        // `keyof typeof Level` は全メンバーのキーを列挙する（型注釈の `Mode` はメンバーを特定しない）
+        assert!(unused_in(&result, "Level.Low").is_empty());
+        assert!(unused_in(&result, "Level.High").is_empty());
+        assert_eq!(unused_in(&result, "Mode.On").len(), 1);
+    }
+
+    #[test]
+    fn test_class_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/services.ts",
+            r#"export class UserService {
+  private cache = new Map();
+  static create() { return new UserService(); }
+  get count() { return this.cache.size; }
+  fetchUser(id: string) { return this.lookup(id); }
+  lookup(id: string) { return this.cache.get(id); }
+  set count(value: number) {}
+  format(value: string): string;
+  format(value: number): string;
+  format(value: unknown) { return String(value); }
+}
+export class LegacyService {}
+export default class ApiError extends Error {}
+"#,
+        );
+        write_file(
+            root,
+            "src/Widget.tsx",
+            "import React from 'react';\nexport class Widget extends React.Component { render() { return null; } }\n",
+        );
+        write_file(
+            root,
+            "src/app.ts",
+            "import { UserService } from './services';\nimport ApiError from './services';\nconst service = UserService.create();\nservice.fetchUser('1');\nthrow new ApiError();\n",
+        );
+
+        let result = detect_in_with(root, |config| {
+            config.detection_types.class_members = true;
+        });
+
+        assert!(unused_in(&result, "UserService").is_empty());
+        assert!(unused_in(&result, "ApiError").is_empty());
+        assert_eq!(unused_in(&result, "LegacyService").len(), 1);
+        let widget = result.unused.iter().find(|e| e.name == "Widget").unwrap();
+        assert_eq!(widget.element_type, ElementType::Component);
+
+        assert!(unused_in(&result, "UserService.create").is_empty());
+        assert!(unused_in(&result, "UserService.fetchUser").is_empty());
+        // `this.lookup` はクラス内部からのアクセス
+        assert_eq!(unused_in(&result, "UserService.lookup").len(), 1);
+        assert_eq!(unused_in(&result, "UserService.count").len(), 1);
+        assert_eq!(unused_in(&result, "UserService.format").len(), 1);
+        assert!(unused_in(&result, "UserService.cache").is_empty());
+        assert!(!result.unused.iter().any(|e| e.name.starts_with("Widget.")));
+    }
+
+    #[test]
+    fn test_property_references_require_class_members() {
+        let content = "const { fetch } = service;\nservice.fetchUser('1');\n";
+        let properties = |class_members: bool| {
+            let config = Config {
+                detection_types: DetectionTypes {
+                    class_members,
+                    ..create_test_config().detection_types
+                },
+                ..create_test_config()
+            };
+            parse_file_for_references_static("app.ts", content, &config)
+                .unwrap()
+                .properties
+                .into_iter()
+                .map(|r| r.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(properties(false).is_empty());
+        assert_eq!(properties(true), vec!["fetch", "fetchUser"]);
+    }
+
+    #[test]
+    fn test_function_classification() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/ui.tsx",
+            r#"import React from 'react';
+export function Button() { return <button />; }
+export function Panel() {
+  const render = () => <div />;
+  return null;
+}
+export const List = function () { return React.createElement('ul'); };
+export const Row = () => (<><td /></>);
+export function useToggle() { return React.useState(false); }
+export function renderRow() { return <tr />; }
+"#,
+        );
+
+        let result = detect_in(root);
+
+        assert_eq!(kind_of(&result, "Button"), Some(ElementType::Component));
+        assert_eq!(kind_of(&result, "List"), Some(ElementType::Component));
+        assert_eq!(kind_of(&result, "Row"), Some(ElementType::Component));
+        assert_eq!(kind_of(&result, "Panel"), Some(ElementType::Function));
+        assert_eq!(kind_of(&result, "useToggle"), Some(ElementType::Hook));
+        assert_eq!(kind_of(&result, "renderRow"), Some(ElementType::Function));
+    }
+
+    #[test]
+    fn test_destructured_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/store.ts",
+            r#"export const { actions, reducer } = createSlice({});
+export const [getValue, setValue] = createStore();
+export const { a: renamed, nested: { deep }, ...rest } = loadConfig();
+export let x, y = 1;
+"#,
+        );
+        write_file(
+            root,
+            "src/main.ts",
+            "import { reducer, getValue, deep } from './store';\nreducer(getValue(), deep);\n",
+        );
+
+        let result = detect_in(root);
+
+        for name in ["reducer", "getValue", "deep"] {
+            assert!(unused_in(&result, name).is_empty(), "{}", name);
+        }
+        for name in ["actions", "setValue", "renamed", "rest", "x", "y"] {
+            assert_eq!(unused_in(&result, name).len(), 1, "{}", name);
+        }
+        let set_value = result.unused.iter().find(|e| e.name == "setValue").unwrap();
+        assert_eq!((set_value.line, set_value.column), (Some(2), Some(25)));
+    }
+
+    #[test]
+    fn test_hook_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/hooks.ts",
+            r#"import { useContext, useEffect, useState } from 'react';
+export function useAuth() { return useContext(AuthContext); }
+export const useDebounce = (value, delay) => {
+  const [debounced, setDebounced] = useState(value);
+  useEffect(() => {}, [value, delay]);
+  return debounced;
+};
+export const useTheme = () => useContext(ThemeContext);
+export function useFormat(value) { return String(value); }
+"#,
+        );
+
+        let result = detect_in(root);
+
+        assert_eq!(kind_of(&result, "useAuth"), Some(ElementType::Hook));
+        assert_eq!(kind_of(&result, "useDebounce"), Some(ElementType::Hook));
+        assert_eq!(kind_of(&result, "useTheme"), Some(ElementType::Hook));
+        // 他のフックを呼び出さない関数はフックではない
+        assert_eq!(kind_of(&result, "useFormat"), Some(ElementType::Function));
+
+        let result = detect_in_with(root, |config| config.detection_types.hooks = false);
+        assert_eq!(kind_of(&result, "useAuth"), None);
+        assert_eq!(kind_of(&result, "useFormat"), Some(ElementType::Function));
+    }
+
+    #[test]
+    fn test_classification_by_shape() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/lib.ts",
+            r#"export const apiClient = createClient();
+export const theme = { color: 'red' };
+export const handler = function () {};
+export const Store = class {};
+export let counter;
+export type props = { id: string };
+export const MAX_RETRIES = 3;
+"#,
+        );
+
+        let result = detect_in(root);
+
+        assert_eq!(kind_of(&result, "apiClient"), Some(ElementType::Variable));
+        assert_eq!(kind_of(&result, "theme"), Some(ElementType::Variable));
+        assert_eq!(kind_of(&result, "handler"), Some(ElementType::Function));
+        assert_eq!(kind_of(&result, "Store"), Some(ElementType::Class));
+        assert_eq!(kind_of(&result, "counter"), Some(ElementType::Variable));
+        assert_eq!(kind_of(&result, "props"), Some(ElementType::Type));
+        assert_eq!(kind_of(&result, "MAX_RETRIES"), Some(ElementType::Variable));
+
+        // 命名規則フィルタを有効にすると従来どおり名前で絞り込む
+        let result = detect_in_with(root, |config| config.naming_filter = true);
+        assert_eq!(kind_of(&result, "apiClient"), None);
+        assert_eq!(kind_of(&result, "theme"), None);
+        assert_eq!(kind_of(&result, "handler"), Some(ElementType::Function));
+        assert_eq!(kind_of(&result, "props"), None);
+        assert_eq!(kind_of(&result, "MAX_RETRIES"), Some(ElementType::Variable));
+    }
+
+    #[test]
+    fn test_definition_and_usage_positions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/ui.tsx",
+            "// 日本語のコメント\nexport function Button() { return <button />; }\n  export type Size = 'sm' | 'lg';\n",
+        );
+        write_file(
+            root,
+            "src/main.tsx",
+            "import { Button } from './ui';\nconst label = '😀'; <Button />;\n",
+        );
+
+        let result = detect_in(root);
+
+        let size = result.unused.iter().find(|e| e.name == "Size").unwrap();
+        assert_eq!((size.line, size.column), (Some(3), Some(15)));
+
+        let button = result
+            .used
+            .iter()
+            .find(|e| e.name == "Button" && e.element_type == ElementType::Component)
+            .unwrap();
+        assert_eq!((button.line, button.column), (Some(2), Some(8)));
+        let jsx_usage = button
+            .usages
+            .iter()
+            .flatten()
+            .flat_map(|usage| &usage.usages)
+            .find(|usage| usage.context == "jsx")
+            .unwrap();
+        // 絵文字はUTF-16で2コード単位
+        assert_eq!((jsx_usage.line, jsx_usage.column), (2, 22));
+    }
+
+    #[test]
+    fn test_local_import_and_public_api_usage_positions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/lib/format.ts",
+            "const PAD = 2;\nexport const pad = (s: string) => s.padStart(PAD);\n",
+        );
+        write_file(root, "src/lib/index.ts", "\nexport { pad } from './format';\n");
+        write_file(
+            root,
+            "src/app.ts",
+            "import { pad } from './lib/format';\n\nconsole.log(pad('a'));\n",
+        );
+
+        let result = detect_in_with(root, |config| {
+            config.detection_types.locals = true;
+            config.public_entry_points = vec!["src/lib/index.ts".to_string()];
+        });
+        let usage_of = |name: &str, element_type: ElementType, context: &str| {
+            result
+                .used
+                .iter()
+                .filter(|e| e.name == name && e.element_type == element_type)
+                .flat_map(|e| e.usages.iter().flatten())
+                .flat_map(|usage| &usage.usages)
+                .find(|usage| usage.context == context)
+                .map(|usage| (usage.line, usage.column))
+        };
+
+        assert_eq!(usage_of("PAD", ElementType::Local, "local"), Some((2, 46)));
+        assert_eq!(usage_of("pad", ElementType::Import, "local"), Some((3, 13)));
+        // バレルから公開されている場合は再エクスポートの位置
+        assert_eq!(usage_of("pad", ElementType::Function, "public API"), Some((2, 10)));
+    }
+
+    #[test]
+    fn test_unused_local_declarations() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/utils.ts",
+            "const RETRIES = 3;\nconst LEGACY_URL = '/v1';\n// @ts-unused-ignore\nconst DEBUG = false;\nconst retry = () => RETRIES;\nexport const fetchAll = () => retry();\n",
+        );
+
+        let result = detect_in_with(root, |config| {
+            config.detection_types.locals = true;
+        });
+
+        let unused_locals: Vec<&str> = result
+            .unused
+            .iter()
+            .filter(|e| e.element_type == ElementType::Local)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(unused_locals, vec!["LEGACY_URL"]);
+        assert!(result
+            .used
+            .iter()
+            .any(|e| e.element_type == ElementType::Local && e.name == "retry"));
+    }
+
+    #[test]
+    fn test_unused_import_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/format.ts", "export const format = (s: string) => s;\nexport const parse = (s: string) => s;\n");
+        write_file(
+            root,
+            "src/app.tsx",
+            "import React from 'react';\nimport { format, parse } from './format';\nexport const App = () => <div>{format('a')}</div>;\n",
+        );
+
+        let result = detect_in(root);
+
+        let unused_imports: Vec<(&str, Option<usize>)> = result
+            .unused
+            .iter()
+            .filter(|e| e.element_type == ElementType::Import)
+            .map(|e| (e.name.as_str(), e.line))
+            .collect();
+        assert_eq!(unused_imports, vec![("parse", Some(2))]);
+    }
+
+    #[test]
+    fn test_unused_react_import_with_automatic_jsx_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "tsconfig.json", r#"{ "compilerOptions": { "jsx": "react-jsx" } }"#);
+        write_file(
+            root,
+            "src/app.tsx",
+            "import React from 'react';\nexport const App = () => <div />;\n",
+        );
+        write_file(
+            root,
+            "src/legacy.tsx",
+            "/** @jsxRuntime classic */\nimport React from 'react';\nexport const Legacy = () => <div />;\n",
+        );
+
+        let result = detect_in(root);
+
+        // tsconfig の `jsx: react-jsx` では `React` は不要（プラグマがあればそちらを優先）
+        let unused_imports: Vec<&str> = result
+            .unused
+            .iter()
+            .filter(|e| e.element_type == ElementType::Import)
+            .flat_map(|e| e.definition_files.iter().map(|f| f.as_str()))
+            .collect();
+        assert_eq!(unused_imports.len(), 1);
+        assert!(unused_imports[0].ends_with("app.tsx"));
+    }
+
+    #[test]
+    fn test_exports_only_used_in_their_own_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/math.ts",
+            "export const square = (n: number) => n * n;\nexport const cube = (n: number) => square(n) * n;\nexport const half = (n: number) => n / 2;\n",
+        );
+        write_file(root, "src/app.ts", "import { cube } from './math';\ncube(2);\n");
+
+        let result = detect_in(root);
+
+        assert!(result.used.iter().any(|e| e.name == "cube"));
+        assert!(result.locally_used.iter().any(|e| e.name == "square"));
+        assert!(unused_in(&result, "square").is_empty());
+        assert_eq!(unused_in(&result, "half").len(), 1);
+    }
+
+    #[test]
+    fn test_recursive_self_references_are_not_local_usages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/tree.ts",
+            r#"export interface TreeNode { children: TreeNode[] }
+export type Json = string | Json[];
+export function walk(node: unknown): void { walk(node); }
+export type Leaf = { value: string };
+export type Branch = { leaves: Leaf[] };
+"#,
+        );
+
+        let result = detect_in(root);
+
+        for name in ["TreeNode", "Json", "walk", "Branch"] {
+            assert_eq!(unused_in(&result, name).len(), 1, "{}", name);
+        }
+        // 別の宣言からの参照はファイル内での使用
+        assert!(result.locally_used.iter().any(|e| e.name == "Leaf"));
+    }
+
+    #[test]
+    fn test_shadowed_locals_are_not_usages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/format.ts",
+            r#"export const formatDate = (date: Date) => date.toISOString();
+export interface User { id: string }
+export const load = (User: unknown) => User;
+export function render() {
+  const formatDate = (value: unknown) => String(value);
+  try { return formatDate(1); } catch (User) { return String(User); }
+}
+"#,
+        );
+        write_file(
+            root,
+            "src/main.ts",
+            "import { load, render } from './format';\nload(render());\n",
+        );
+
+        let result = detect_in(root);
+
+        assert!(unused_in(&result, "load").is_empty());
+        assert!(unused_in(&result, "render").is_empty());
+        // 同名の引数・ローカル変数への参照はエクスポートの使用ではない
+        assert_eq!(unused_in(&result, "formatDate").len(), 1);
+        assert_eq!(unused_in(&result, "User").len(), 1);
+        assert!(result.locally_used.is_empty());
+    }
+
+    #[test]
+    fn test_type_params_and_hoisted_vars_shadow_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/format.ts",
+            r#"export interface User { id: string }
+export const formatDate = (date: Date) => date.toISOString();
+export function identity<User>(value: User): User { return value; }
+export type Wrap<User> = { value: User };
+export function legacy() {
+  if (Date.now()) { var formatDate = ''; }
+  return formatDate;
+}
+"#,
+        );
+        write_file(
+            root,
+            "src/models.ts",
+            "export interface Account { id: string }\nexport const formatTime = () => '';\n",
+        );
+        write_file(
+            root,
+            "src/app.ts",
+            r#"import { identity, legacy } from './format';
+import type { Wrap } from './format';
+import { Account, formatTime } from './models';
+export function wrap<Account>(value: Account): Wrap<Account> { return { value: identity(value) }; }
+export function run() {
+  for (;;) { var formatTime = legacy(); break; }
+  return formatTime;
+}
+"#,
+        );
+
+        let result = detect_in(root);
+
+        // 型引数・巻き上げられた `var` への参照は同名のエクスポートやimportの使用ではない
+        assert_eq!(unused_in(&result, "User").len(), 1);
+        assert_eq!(unused_in(&result, "formatDate").len(), 1);
+        assert!(result.locally_used.is_empty());
+        let unused_imports: Vec<&str> = result
+            .unused
+            .iter()
+            .filter(|e| e.element_type == ElementType::Import)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(unused_imports, vec!["Account", "formatTime"]);
+    }
+
+    #[test]
+    fn test_infer_mapped_and_setter_bindings_shadow_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/models.ts",
+            "export interface User { id: string }\nexport type Key = 'id';\nexport const token = '';\n",
+        );
+        write_file(
+            root,
+            "src/app.ts",
+            r#"import { User, Key, token } from './models';
+export type Elem<T> = T extends Array<infer User> ? User : never;
+export type Keys<T> = { [Key in keyof T]: Key };
+export const store = { set value(token: string) { console.log(token); } };
+"#,
+        );
+
+        let result = detect_in(root);
+
+        // `infer`・mapped type のキー・セッターの引数は同名のimportを隠す
+        assert_eq!(unused_in(&result, "User").len(), 1);
+        assert_eq!(unused_in(&result, "Key").len(), 1);
+        assert_eq!(unused_in(&result, "token").len(), 1);
+        let unused_imports: Vec<&str> = result
+            .unused
+            .iter()
+            .filter(|e| e.element_type == ElementType::Import)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(unused_imports, vec!["User", "Key", "token"]);
+    }
+
+    #[test]
+    fn test_class_expression_and_namespace_bindings_shadow_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/util.ts", "export class Helper {}\nexport const Other = 2;\n");
+        write_file(
+            root,
+            "src/main.ts",
+            "import { Helper, Other } from './util';\nexport const K = class Helper { m() { return new Helper(); } };\nexport namespace NS { const Other = 1; export const y = Other; }\n",
+        );
+
+        let result = detect_in(root);
+
+        // クラス式の名前・namespace 内の宣言は同名のimportを隠す
+        assert_eq!(unused_in(&result, "Helper").len(), 1);
+        assert_eq!(unused_in(&result, "Other").len(), 1);
+    }
+
+    #[test]
+    fn test_class_expression_and_namespace_bindings_shadow_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "src/main.ts",
+            "export class Helper {}\nexport const Other = 2;\nexport const K = class Helper { m() { return new Helper(); } };\nexport namespace NS { const Other = 1; export const y = Other; }\n",
+        );
+
+        let result = detect_in(root);
+
+        // クラス式の名前・namespace 内の宣言への参照は同名のエクスポートの使用ではない
+        assert_eq!(unused_in(&result, "Helper").len(), 1);
+        assert_eq!(unused_in(&result, "Other").len(), 1);
+    }
+
+    #[test]
+    fn test_default_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "src/components/Card/index.tsx", "export default () => <div />;\n");
+        write_file(root, "src/user-card.tsx", "export default () => <div />;\n");
+        write_file(root, "src/api-client.ts", "export default class {}\n");
+        write_file(
+            root,
+            "src/Header.tsx",
+            "import { memo } from 'react';\nconst Header = () => <header />;\nexport default memo(Header);\n",
+        );
+        write_file(root, "src/ApiClient.ts", "export default class ApiClient { fetchAll() {} }\n");
+        write_file(
+            root,
+            "src/Foo.tsx",
+            "import { memo, forwardRef } from 'react';\nconst Inner = () => <div />;\nexport default memo(forwardRef(Inner));\n",
+        );
+        write_file(
+            root,
+            "src/Footer.tsx",
+            "function Footer() { return <footer />; }\nexport { Footer as default };\n",
+        );
+        write_file(root, "src/Sidebar.tsx", "export default function Sidebar() { return <aside />; }\n");
+        write_file(root, "src/Nav.tsx", "export const Nav = () => <nav />;\n");
+        write_file(
+            root,
+            "src/Button.tsx",
+            "export function Button() { return <button />; }\nexport default Button;\n",
+        );
+        write_file(
+            root,
+            "src/Badge.tsx",
+            "export function Badge() { return <span />; }\nexport { Badge as default };\n",
+        );
+        write_file(
+            root,
+            "src/app.tsx",
+            r#"import TopBar from './Header';
+import Client from './ApiClient';
+import Nav from './Sidebar';
+import Btn from './Button';
+import Tag from './Badge';
+new Client();
+export const App = () => <><TopBar /><Nav /><Btn /><Tag /></>;
+"#,
+        );
+
+        let result = detect_in(root);
+
+        for name in ["Header", "ApiClient", "Sidebar"] {
+            assert!(unused_in(&result, name).is_empty(), "{}", name);
+        }
+        // ローカル名が一致しても、別モジュールの default import は使用扱いにしない
+        assert_eq!(unused_in(&result, "Nav").len(), 1);
+        assert_eq!(unused_in(&result, "Footer").len(), 1);
+
+        let card = result.unused.iter().find(|e| e.name == "Card").unwrap();
+        assert_eq!(card.element_type, ElementType::Component);
+        assert_eq!(card.export_name.as_deref(), Some("default"));
+        assert_eq!((card.line, card.column), (Some(1), Some(1)));
+        // 無名の default エクスポートはファイル名ではなく形で種類を判定する
+        assert_eq!(kind_of(&result, "user-card"), Some(ElementType::Component));
+        assert_eq!(kind_of(&result, "api-client"), Some(ElementType::Class));
+        let filtered = detect_in_with(root, |config| config.naming_filter = true);
+        assert_eq!(kind_of(&filtered, "user-card"), Some(ElementType::Component));
+        assert_eq!(kind_of(&filtered, "api-client"), Some(ElementType::Class));
+        let header = result.used.iter().find(|e| e.name == "Header").unwrap();
+        assert_eq!(header.element_type, ElementType::Component);
+        // `memo(Inner)` はファイル名ではなく包んだコンポーネントの名前で表示する
+        let inner = result.unused.iter().find(|e| e.name == "Inner").unwrap();
+        assert_eq!(inner.element_type, ElementType::Component);
+        assert_eq!(inner.export_name.as_deref(), Some("default"));
+        assert!(unused_in(&result, "Foo").is_empty());
+        // default エクスポートのクラスのメンバーは default として扱わない
+        let with_members = detect_in_with(root, |config| config.detection_types.class_members = true);
+        let member = with_members
+            .unused
+            .iter()
+            .find(|e| e.name == "ApiClient.fetchAll")
+            .unwrap();
+        assert_eq!(member.export_name, None);
+        // 使われている default export が参照する名前付きエクスポートは export キーワードだけが不要
+        for name in ["Button", "Badge"] {
+            assert!(unused_in(&result, name).is_empty(), "{}", name);
+            let named = result
+                .locally_used
+                .iter()
+                .find(|e| e.name == name && e.export_name.is_none())
+                .unwrap();
+            let usages = named.usages.as_ref().unwrap();
+            assert_eq!(usages[0].usages[0].context, "export default");
+        }
+    }
+}
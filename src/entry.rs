@@ -0,0 +1,129 @@
+use crate::resolver::normalize_path;
+use crate::types::DetectorError;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// エントリーポイント（importされなくても使用中とみなすファイル）の判定
+#[derive(Debug, Clone)]
+pub struct EntryMatcher {
+    globs: GlobSet,
+    /// HTML等から見つかった個別のエントリーファイル
+    files: HashSet<PathBuf>,
+}
+
+impl EntryMatcher {
+    /// globパターンから作成
+    ///
+    /// `**/` で始まらないパターンは任意のディレクトリ配下にも一致させる
+    /// （`src/main.tsx` は `apps/web/src/main.tsx` にも一致する）
+    pub fn new(patterns: &[String]) -> Result<Self, DetectorError> {
+        Self::with_anchored(patterns, &[])
+    }
+
+    /// globパターンと、位置を固定したglobパターンから作成
+    ///
+    /// `anchored` のパターン（package.json から求めたもの）には `**/` を補わない。
+    /// ルートの `"main": "index.js"` がネストした `index.ts` にまで一致しないようにするため
+    pub fn with_anchored(patterns: &[String], anchored: &[String]) -> Result<Self, DetectorError> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim_start_matches("./");
+            builder.add(compile_glob(pattern)?);
+            if !pattern.starts_with("**/") && !pattern.starts_with('/') {
+                builder.add(compile_glob(&format!("**/{}", pattern))?);
+            }
+        }
+        for pattern in anchored {
+            builder.add(compile_glob(pattern.trim_start_matches("./"))?);
+        }
+
+        let globs = builder.build().map_err(|e| DetectorError::Config {
+            message: format!("Invalid entry point patterns: {}", e),
+        })?;
+        Ok(Self {
+            globs,
+            files: HashSet::new(),
+        })
+    }
+
+    /// 個別のエントリーファイルを追加する
+    pub fn with_files(mut self, files: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.files
+            .extend(files.into_iter().map(|file| normalize_path(&file)));
+        self
+    }
+
+    /// ファイルがエントリーポイントかどうか
+    pub fn is_entry(&self, file: &str) -> bool {
+        let path = Path::new(file.trim_start_matches("./"));
+        self.globs.is_match(path)
+            || (!self.files.is_empty() && self.files.contains(&normalize_path(path)))
+    }
+}
+
+fn compile_glob(pattern: &str) -> Result<Glob, DetectorError> {
+    globset::GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| DetectorError::Config {
+            message: format!("Invalid entry point pattern '{}': {}", pattern, e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::default_entry_points;
+
+    #[test]
+    fn test_default_entry_points() {
+        let matcher = EntryMatcher::new(&default_entry_points()).unwrap();
+
+        assert!(matcher.is_entry("src/main.tsx"));
+        assert!(matcher.is_entry("apps/web/src/index.ts"));
+        assert!(matcher.is_entry("app/dashboard/page.tsx"));
+        assert!(matcher.is_entry("vite.config.ts"));
+        assert!(!matcher.is_entry("src/components/index.ts"));
+        assert!(!matcher.is_entry("src/utils/api.ts"));
+    }
+
+    #[test]
+    fn test_custom_entry_points() {
+        let patterns = vec!["./src/routes/*.tsx".to_string()];
+        let matcher = EntryMatcher::new(&patterns).unwrap();
+
+        assert!(matcher.is_entry("src/routes/home.tsx"));
+        assert!(matcher.is_entry("packages/app/src/routes/home.tsx"));
+        assert!(!matcher.is_entry("src/routes/nested/home.tsx"));
+    }
+
+    #[test]
+    fn test_anchored_entry_points() {
+        let matcher =
+            EntryMatcher::with_anchored(&[], &["index.{ts,tsx}".to_string(), "apps/web/src/lib.ts".to_string()])
+                .unwrap();
+
+        assert!(matcher.is_entry("index.ts"));
+        assert!(matcher.is_entry("./index.tsx"));
+        assert!(!matcher.is_entry("src/utils/index.ts"));
+        assert!(!matcher.is_entry("src/components/index.tsx"));
+        assert!(matcher.is_entry("apps/web/src/lib.ts"));
+        assert!(!matcher.is_entry("apps/web/src/nested/apps/web/src/lib.ts"));
+    }
+
+    #[test]
+    fn test_entry_files() {
+        let matcher = EntryMatcher::new(&[])
+            .unwrap()
+            .with_files(vec![PathBuf::from("web/src/main.tsx")]);
+
+        assert!(matcher.is_entry("./web/src/main.tsx"));
+        assert!(!matcher.is_entry("src/main.tsx"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(EntryMatcher::new(&["src/{main".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::resolver::normalize_path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// HTMLファイルから読み込まれるスクリプト（エントリーポイント）を集める
+///
+/// 存在しないHTMLファイルは無視する
+pub fn html_entry_files(html_files: &[String]) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = html_files
+        .iter()
+        .filter_map(|html| {
+            let path = Path::new(html);
+            let content = fs::read_to_string(path).ok()?;
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            Some(
+                script_sources(&content)
+                    .into_iter()
+                    .filter_map(|src| resolve_script_src(dir, &src))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect();
+    entries.sort();
+    entries.dedup();
+    entries
+}
+
+/// `<script type="module" src>` と `<link rel="modulepreload" href>` の値を抽出する
+///
+/// `type` のない従来のスクリプトはバンドラーの対象外（そのまま配信される）なので含めない
+pub fn script_sources(content: &str) -> Vec<String> {
+    let lower = content.to_ascii_lowercase();
+    let mut sources = Vec::new();
+    let mut pos = 0;
+
+    while let Some(start) = lower[pos..].find('<').map(|i| pos + i) {
+        let Some(end) = lower[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        pos = end + 1;
+
+        let tag = &content[start + 1..end];
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let attributes = tag_attributes(&tag[name_end..]);
+
+        let name = tag[..name_end].to_ascii_lowercase();
+        let source = match name.as_str() {
+            "script" => attributes
+                .get("type")
+                .filter(|kind| kind.trim().eq_ignore_ascii_case("module"))
+                .and(attributes.get("src")),
+            "link" => attributes
+                .get("rel")
+                .filter(|rel| {
+                    rel.split_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("modulepreload"))
+                })
+                .and(attributes.get("href")),
+            _ => None,
+        };
+        if let Some(source) = source {
+            sources.push(source.clone());
+        }
+
+        // scriptの中身はタグとして解釈しない
+        if name == "script" {
+            match lower[pos..].find("</script") {
+                Some(close) => pos += close,
+                None => break,
+            }
+        }
+    }
+
+    sources
+}
+
+/// タグの属性を (小文字の名前 → 値) として取り出す
+fn tag_attributes(tag: &str) -> HashMap<String, String> {
+    let chars: Vec<char> = tag.chars().collect();
+    let mut attributes = HashMap::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i].is_whitespace() || chars[i] == '/' {
+            i += 1;
+            continue;
+        }
+
+        let name_start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '=' && chars[i] != '/' {
+            i += 1;
+        }
+        let name: String = chars[name_start..i]
+            .iter()
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if chars.get(i) != Some(&'=') {
+            attributes.insert(name, String::new());
+            continue;
+        }
+        i += 1;
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let value: String = match chars.get(i) {
+            Some(&quote) if quote == '"' || quote == '\'' => {
+                let value_start = i + 1;
+                i = value_start;
+                while i < chars.len() && chars[i] != quote {
+                    i += 1;
+                }
+                let value = chars[value_start..i.min(chars.len())].iter().collect();
+                i += 1;
+                value
+            }
+            _ => {
+                let value_start = i;
+                while i < chars.len() && !chars[i].is_whitespace() {
+                    i += 1;
+                }
+                chars[value_start..i].iter().collect()
+            }
+        };
+        attributes.insert(name, value);
+    }
+
+    attributes
+}
+
+/// スクリプトのURLをファイルパスへ変換する（`/` 始まりはHTMLのディレクトリを基準とする）
+fn resolve_script_src(html_dir: &Path, src: &str) -> Option<PathBuf> {
+    let src = src.split(['?', '#']).next().unwrap_or_default().trim();
+    if src.is_empty() || src.starts_with("//") || src.contains("://") || src.starts_with("data:") {
+        return None;
+    }
+    Some(normalize_path(&html_dir.join(src.trim_start_matches('/'))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_script_sources() {
+        let html = r#"<!doctype html>
+<html>
+  <head>
+    <link rel="icon" href="/vite.svg" />
+    <link rel="modulepreload" href="./src/vendor.ts">
+    <SCRIPT TYPE="MODULE" src='https://cdn.example.com/lib.js'></SCRIPT>
+  </head>
+  <body>
+    <script type="module">
+      const tag = "<script src='/src/not-an-entry.ts'>";
+    </script>
+    <script type="module" src="/src/main.tsx?v=1"></script>
+  </body>
+</html>"#;
+
+        assert_eq!(
+            script_sources(html),
+            vec![
+                "./src/vendor.ts",
+                "https://cdn.example.com/lib.js",
+                "/src/main.tsx?v=1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classic_scripts_are_not_entries() {
+        let html = r#"<script src="/legacy/analytics.js"></script>
+<script type="text/javascript" src="/legacy/polyfill.js"></script>
+<script type="module" src="/src/main.ts"></script>"#;
+
+        assert_eq!(script_sources(html), vec!["/src/main.ts"]);
+    }
+
+    #[test]
+    fn test_script_tag_name_must_match_exactly() {
+        // `<scripts>` などは script 要素ではないので、続くタグも読み飛ばさない
+        let html = r#"<scripts src="/src/ignored.ts"></scripts>
+<scripting-panel>
+  <script type="module" src="/src/main.ts"></script>
+</scripting-panel>"#;
+
+        assert_eq!(script_sources(html), vec!["/src/main.ts"]);
+    }
+
+    #[test]
+    fn test_html_entry_files() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("web")).unwrap();
+        fs::write(
+            root.join("web/index.html"),
+            r#"<script type="module" src="/src/main.tsx"></script>
+<script src="https://cdn.example.com/lib.js"></script>"#,
+        )
+        .unwrap();
+
+        let html_files = vec![
+            root.join("web/index.html").to_string_lossy().to_string(),
+            root.join("missing.html").to_string_lossy().to_string(),
+        ];
+        assert_eq!(
+            html_entry_files(&html_files),
+            vec![normalize_path(&root.join("web/src/main.tsx"))]
+        );
+    }
+}
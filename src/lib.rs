@@ -1,6 +1,13 @@
 pub mod config;
 pub mod detector;
+pub mod entry;
+pub mod html;
+pub mod locals;
+pub mod location;
 pub mod reporter;
+pub mod resolver;
+pub mod scope;
+pub mod tsconfig;
 pub mod types;
 
 pub use config::{adjust_config_for_monorepo, load_config};
@@ -34,7 +41,10 @@ pub fn detect_unused_elements(
 }
 
 /// 設定をマージする
+///
+/// カスタム設定の各項目は、空のリストやデフォルト値のままなら未指定とみなして基本設定の値を使う
 fn merge_configs(base: Config, custom: Config) -> Config {
+    let defaults = Config::default();
     Config {
         search_dirs: if custom.search_dirs.is_empty() {
             base.search_dirs
@@ -51,6 +61,41 @@ fn merge_configs(base: Config, custom: Config) -> Config {
             patterns.dedup();
             patterns
         },
+        extensions: if custom.extensions.is_empty() {
+            base.extensions
+        } else {
+            custom.extensions
+        },
+        jsx_in_js: if custom.jsx_in_js == defaults.jsx_in_js {
+            base.jsx_in_js
+        } else {
+            custom.jsx_in_js
+        },
+        entry_points: if custom.entry_points.is_empty() {
+            base.entry_points
+        } else {
+            custom.entry_points
+        },
+        public_entry_points: if custom.public_entry_points.is_empty() {
+            base.public_entry_points
+        } else {
+            custom.public_entry_points
+        },
+        package_entry_points: if custom.package_entry_points.is_empty() {
+            base.package_entry_points
+        } else {
+            custom.package_entry_points
+        },
+        html_entry_files: if custom.html_entry_files.is_empty() {
+            base.html_entry_files
+        } else {
+            custom.html_entry_files
+        },
+        naming_filter: if custom.naming_filter == defaults.naming_filter {
+            base.naming_filter
+        } else {
+            custom.naming_filter
+        },
         detection_types: custom.detection_types,
         ci: custom.ci.or(base.ci),
     }
@@ -66,11 +111,42 @@ mod tests {
         let custom = Config {
             search_dirs: vec!["custom/src".to_string()],
             exclude_patterns: vec![],
+            extensions: vec![],
+            jsx_in_js: true,
+            entry_points: vec![],
+            public_entry_points: vec![],
+            package_entry_points: vec![],
+            html_entry_files: vec![],
+            naming_filter: false,
             detection_types: DetectionTypes::default(),
             ci: None,
         };
 
         let merged = merge_configs(base.clone(), custom);
         assert_eq!(merged.search_dirs, vec!["custom/src"]);
+        assert_eq!(merged.extensions, default_extensions());
+    }
+
+    #[test]
+    fn test_merge_configs_keeps_base_flags_unless_overridden() {
+        let base = Config {
+            jsx_in_js: false,
+            naming_filter: true,
+            ..Config::default()
+        };
+
+        // デフォルト値のままのカスタム設定は基本設定のフラグを上書きしない
+        let merged = merge_configs(base.clone(), Config::default());
+        assert!(!merged.jsx_in_js);
+        assert!(merged.naming_filter);
+
+        let custom = Config {
+            jsx_in_js: false,
+            naming_filter: true,
+            ..Config::default()
+        };
+        let merged = merge_configs(Config::default(), custom);
+        assert!(!merged.jsx_in_js);
+        assert!(merged.naming_filter);
     }
 }
@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::scope::Scopes;
+use crate::tsconfig::TsConfig;
+
+/// エクスポートされていないトップレベル宣言
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalDeclaration {
+    pub name: String,
+    pub span: Span,
+    /// 自身の宣言の外からのファイル内で最初の参照（使われていなければ None）
+    pub usage: Option<Span>,
+}
+
+/// モジュール内部（非エクスポート）のトップレベル宣言と、そのファイル内での使用有無を求める
+///
+/// `scopes` で内側のスコープの同名の束縛に解決される参照は数えない
+pub fn local_declarations(module: &Module, scopes: &Scopes) -> Vec<LocalDeclaration> {
+    let declarations: Vec<(String, Span)> = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) if !is_ambient(decl) => Some(declared_names(decl)),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    if declarations.is_empty() {
+        return Vec::new();
+    }
+
+    let visitor = scan(module, scopes, &declarations);
+    into_declarations(declarations, &visitor.used)
+}
+
+/// importで導入された束縛と、そのファイル内での使用有無を求める
+///
+/// `jsx_factory`（クラシックJSXランタイムの `React` 等）はJSXがあれば使用中とみなす
+pub fn import_bindings(
+    module: &Module,
+    scopes: &Scopes,
+    jsx_factory: Option<&str>,
+) -> Vec<LocalDeclaration> {
+    let bindings: Vec<(String, Span)> = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                Some(import_decl.specifiers.iter().map(|specifier| {
+                    let local = match specifier {
+                        ImportSpecifier::Named(named) => &named.local,
+                        ImportSpecifier::Default(default) => &default.local,
+                        ImportSpecifier::Namespace(namespace) => &namespace.local,
+                    };
+                    (local.sym.to_string(), specifier.span())
+                }))
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    if bindings.is_empty() {
+        return Vec::new();
+    }
+
+    let mut visitor = scan(module, scopes, &bindings);
+    if let (Some(jsx), Some(factory)) = (visitor.jsx, jsx_factory) {
+        visitor.used.entry(factory.to_string()).or_insert(jsx);
+    }
+    into_declarations(bindings, &visitor.used)
+}
+
+/// ファイルがクラシックJSXランタイムなら、JSXの変換先となる識別子を返す
+///
+/// `@jsxRuntime` / `@jsx h` プラグマがあればそれに従い、なければ tsconfig の `jsx` で決める
+/// （`react-jsx` / `react-jsxdev` は自動ランタイム）。クラシックランタイムの既定は `React`
+pub fn classic_jsx_factory(source: &str, tsconfig: Option<&TsConfig>) -> Option<String> {
+    if source.contains("@jsxRuntime automatic") {
+        return None;
+    }
+    let pragma = source.match_indices("@jsx ").find_map(|(pos, pragma)| {
+        let name = source[pos + pragma.len()..].split_whitespace().next()?;
+        name.split('.').next().map(|name| name.to_string())
+    });
+    if pragma.is_some() {
+        return pragma;
+    }
+    let automatic = !source.contains("@jsxRuntime classic")
+        && tsconfig.is_some_and(TsConfig::uses_automatic_jsx);
+    (!automatic).then(|| "React".to_string())
+}
+
+/// `names` への参照をモジュール全体から集める
+fn scan<'a>(module: &Module, scopes: &'a Scopes, names: &[(String, Span)]) -> UsageVisitor<'a> {
+    let mut declarations: HashMap<String, Vec<Span>> = HashMap::new();
+    for (name, span) in names {
+        declarations.entry(name.clone()).or_default().push(*span);
+    }
+    let mut visitor = UsageVisitor {
+        scopes,
+        declarations,
+        used: HashMap::new(),
+        jsx: None,
+    };
+    module.visit_with(&mut visitor);
+    visitor
+}
+
+fn into_declarations(names: Vec<(String, Span)>, used: &HashMap<String, Span>) -> Vec<LocalDeclaration> {
+    names
+        .into_iter()
+        .map(|(name, span)| LocalDeclaration {
+            usage: used.get(&name).copied(),
+            name,
+            span,
+        })
+        .collect()
+}
+
+/// `declare` による宣言（実体を持たない）かどうか
+fn is_ambient(decl: &Decl) -> bool {
+    match decl {
+        Decl::Fn(func) => func.declare,
+        Decl::Class(class) => class.declare,
+        Decl::Var(var) => var.declare,
+        Decl::TsInterface(interface) => interface.declare,
+        Decl::TsTypeAlias(alias) => alias.declare,
+        Decl::TsEnum(enum_decl) => enum_decl.declare,
+        _ => true,
+    }
+}
+
+/// 宣言が導入する名前
+fn declared_names(decl: &Decl) -> Vec<(String, Span)> {
+    match decl {
+        Decl::Fn(func) => vec![(func.ident.sym.to_string(), func.span())],
+        Decl::Class(class) => vec![(class.ident.sym.to_string(), class.span())],
+        Decl::Var(var) => var
+            .decls
+            .iter()
+            .flat_map(|declarator| {
+                let mut names = Vec::new();
+                pat_names(&declarator.name, &mut names);
+                names.into_iter().map(|name| (name, declarator.span))
+            })
+            .collect(),
+        Decl::TsInterface(interface) => vec![(interface.id.sym.to_string(), interface.span)],
+        Decl::TsTypeAlias(alias) => vec![(alias.id.sym.to_string(), alias.span)],
+        Decl::TsEnum(enum_decl) => vec![(enum_decl.id.sym.to_string(), enum_decl.span)],
+        _ => Vec::new(),
+    }
+}
+
+/// パターンが束縛する名前を集める
+fn pat_names(pat: &Pat, names: &mut Vec<String>) {
+    names.extend(pat_idents(pat).into_iter().map(|ident| ident.sym.to_string()));
+}
+
+/// パターン（分割代入・rest・デフォルト値を含む）が束縛する識別子
+pub fn pat_idents(pat: &Pat) -> Vec<&Ident> {
+    match pat {
+        Pat::Ident(ident) => vec![&ident.id],
+        Pat::Array(array) => array.elems.iter().flatten().flat_map(pat_idents).collect(),
+        Pat::Object(object) => object
+            .props
+            .iter()
+            .flat_map(|prop| match prop {
+                ObjectPatProp::KeyValue(key_value) => pat_idents(&key_value.value),
+                ObjectPatProp::Assign(assign) => vec![&assign.key.id],
+                ObjectPatProp::Rest(rest) => pat_idents(&rest.arg),
+            })
+            .collect(),
+        Pat::Rest(rest) => pat_idents(&rest.arg),
+        Pat::Assign(assign) => pat_idents(&assign.left),
+        Pat::Invalid(_) | Pat::Expr(_) => Vec::new(),
+    }
+}
+
+/// トップレベルの束縛に解決される参照を集めるVisitor
+struct UsageVisitor<'a> {
+    scopes: &'a Scopes,
+    /// 名前 → その名前を宣言する範囲（宣言子・関数全体など）
+    declarations: HashMap<String, Vec<Span>>,
+    /// 名前 → 最初の参照
+    used: HashMap<String, Span>,
+    /// 最初のJSX（JSXを含まなければ None）
+    jsx: Option<Span>,
+}
+
+impl UsageVisitor<'_> {
+    fn record(&mut self, ident: &Ident) {
+        let name = ident.sym.as_ref();
+        if self.scopes.is_scoped(ident) {
+            return;
+        }
+        // 再帰呼び出しなど、自身の宣言内からの参照は使用とみなさない
+        // （`const a = 1, b = a;` の `a` は別の宣言子からの参照なので使用）
+        let Some(spans) = self.declarations.get(name) else {
+            return;
+        };
+        if !spans.iter().any(|span| span.contains(ident.span)) {
+            self.used.entry(name.to_string()).or_insert(ident.span);
+        }
+    }
+}
+
+impl Visit for UsageVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Ident(ident) = expr {
+            self.record(ident);
+        }
+        expr.visit_children_with(self);
+    }
+
+    fn visit_prop(&mut self, prop: &Prop) {
+        if let Prop::Shorthand(ident) = prop {
+            self.record(ident);
+        }
+        prop.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, element: &JSXElement) {
+        self.jsx.get_or_insert(element.span);
+        element.visit_children_with(self);
+    }
+
+    fn visit_jsx_fragment(&mut self, fragment: &JSXFragment) {
+        self.jsx.get_or_insert(fragment.span);
+        fragment.visit_children_with(self);
+    }
+
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        match name {
+            // 小文字始まりは組み込み要素
+            JSXElementName::Ident(ident) if !ident.sym.starts_with(|c: char| c.is_lowercase()) => {
+                self.record(ident)
+            }
+            JSXElementName::JSXMemberExpr(member) => {
+                let mut obj = &member.obj;
+                while let JSXObject::JSXMemberExpr(inner) = obj {
+                    obj = &inner.obj;
+                }
+                if let JSXObject::Ident(ident) = obj {
+                    self.record(ident);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_ts_entity_name(&mut self, name: &TsEntityName) {
+        let mut name = name;
+        while let TsEntityName::TsQualifiedName(qualified) = name {
+            name = &qualified.left;
+        }
+        if let TsEntityName::Ident(ident) = name {
+            self.record(ident);
+        }
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        // `export { a } from './x'` の `a` は別モジュールの名前
+        if export.src.is_some() {
+            return;
+        }
+        for specifier in &export.specifiers {
+            if let ExportSpecifier::Named(ExportNamedSpecifier {
+                orig: ModuleExportName::Ident(ident),
+                ..
+            }) = specifier
+            {
+                self.record(ident);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::{sync::Lrc, FileName, SourceMap};
+    use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+
+    fn parse(source: &str) -> (Module, Scopes) {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon, source.to_string());
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig {
+                tsx: true,
+                ..Default::default()
+            }),
+            Default::default(),
+            StringInput::from(&*fm),
+            None,
+        );
+        let mut module = Parser::new_from(lexer).parse_module().unwrap();
+        let scopes = Scopes::resolve(&mut module, true);
+        (module, scopes)
+    }
+
+    fn unused_locals(source: &str) -> Vec<String> {
+        let (module, scopes) = parse(source);
+        local_declarations(&module, &scopes)
+            .into_iter()
+            .filter(|decl| decl.usage.is_none())
+            .map(|decl| decl.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_local_declarations() {
+        let source = r#"
+const API_URL = "https://example.com";
+const TIMEOUT = 1000;
+type Options = { retries: number };
+interface Legacy { id: string }
+function request(options: Options) { return fetch(API_URL); }
+function walk(n: number): number { return n > 0 ? walk(n - 1) : 0; }
+const Icon = () => null;
+export const load = () => { const TIMEOUT = 5; return request({ retries: TIMEOUT }); };
+export const View = () => <Icon />;
+"#;
+
+        assert_eq!(unused_locals(source), vec!["TIMEOUT", "Legacy", "walk"]);
+    }
+
+    #[test]
+    fn test_references_between_declarators() {
+        let source = "const a = 1, b = a;\nconst c = () => c(), d = 2;\nexport const e = b;\n";
+
+        assert_eq!(unused_locals(source), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn test_shadowed_references_are_ignored() {
+        let source = r#"
+const config = {};
+const format = (value: string) => value;
+function render(config: object) { return config; }
+export function run(items: string[]) {
+  try { render({}); } catch (format) { console.log(format); }
+  for (const format of items) { console.log(format); }
+}
+export { };
+"#;
+
+        assert_eq!(unused_locals(source), vec!["config", "format"]);
+    }
+
+    #[test]
+    fn test_type_params_and_hoisted_vars_shadow() {
+        let source = r#"
+type Options = { retries: number };
+const value = 1;
+export function run<Options>(options: Options) {
+  if (options) { var value = 2; }
+  return value;
+}
+export type Wrap<Options> = { inner: Options };
+"#;
+
+        assert_eq!(unused_locals(source), vec!["Options", "value"]);
+    }
+
+    #[test]
+    fn test_infer_mapped_and_setter_bindings_shadow() {
+        let source = r#"
+type Item = string;
+type Key = 'id';
+type Fallback = number;
+const token = 1;
+export type Elem<T> = T extends Array<infer Item> ? Item : Fallback;
+export type Keys<T> = { [Key in keyof T]: Key };
+export const store = { set value(token: number) { console.log(token); } };
+"#;
+
+        assert_eq!(unused_locals(source), vec!["Item", "Key", "token"]);
+    }
+
+    #[test]
+    fn test_class_expression_and_namespace_bindings_shadow() {
+        let source = "const Helper = 1;\nconst Other = 2;\nexport const K = class Helper { m() { return new Helper(); } };\nexport namespace NS { const Other = 1; export const y = Other; }\n";
+
+        assert_eq!(unused_locals(source), vec!["Helper", "Other"]);
+    }
+
+    #[test]
+    fn test_local_export_specifiers_count_as_usage() {
+        let source = "const helper = () => 1;\nconst other = 2;\nexport { helper };\nexport { other } from './other';\n";
+
+        assert_eq!(unused_locals(source), vec!["other"]);
+    }
+
+    fn unused_imports(source: &str) -> Vec<String> {
+        let (module, scopes) = parse(source);
+        import_bindings(&module, &scopes, classic_jsx_factory(source, None).as_deref())
+            .into_iter()
+            .filter(|binding| binding.usage.is_none())
+            .map(|binding| binding.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_imports() {
+        let source = r#"
+import React, { useState, useEffect } from 'react';
+import type { User } from './types';
+import { Button, Card } from './components';
+import * as api from './api';
+import { format } from './format';
+
+export function Profile({ user }: { user: User }) {
+  const [state] = useState(api.load());
+  const format = (s: string) => s;
+  return <Button>{format(state)}</Button>;
+}
+"#;
+
+        assert_eq!(unused_imports(source), vec!["useEffect", "Card", "format"]);
+    }
+
+    #[test]
+    fn test_jsx_factory_pragmas() {
+        let automatic = "/** @jsxRuntime automatic */\nimport React from 'react';\nexport const A = () => <div />;\n";
+        assert_eq!(unused_imports(automatic), vec!["React"]);
+
+        let preact =
+            "/** @jsx h */\nimport { h } from 'preact';\nexport const A = () => <div />;\n";
+        assert!(unused_imports(preact).is_empty());
+
+        let no_jsx = "import React from 'react';\nexport const a = 1;\n";
+        assert_eq!(unused_imports(no_jsx), vec!["React"]);
+    }
+}
@@ -0,0 +1,95 @@
+use swc_common::BytePos;
+
+/// ソース上の位置（1始まりの行・列）
+///
+/// 列はエディタ（LSP）に合わせてUTF-16のコード単位で数える
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// パース時のバイトオフセット（`BytePos`）を行・列に変換するための索引
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    text: String,
+    /// 各行の先頭のバイトオフセット
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            text: text.to_string(),
+            line_starts,
+        }
+    }
+
+    /// `BytePos(0)` から始まる入力として解析したソースの位置を返す
+    pub fn position(&self, pos: BytePos) -> Position {
+        let offset = (pos.0 as usize).min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let column = self
+            .text
+            .get(line_start..offset)
+            .map_or(0, |prefix| prefix.encode_utf16().count());
+        Position {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+
+    /// 1始まりの行番号の行を改行文字を除いて返す
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.text.len(), |next| next - 1);
+        let text = &self.text[start..end];
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(text: &str, offset: usize) -> (usize, usize) {
+        let position = LineIndex::new(text).position(BytePos(offset as u32));
+        (position.line, position.column)
+    }
+
+    #[test]
+    fn test_position() {
+        let text = "const a = 1;\nconst b = 2;\r\nexport { b };\n";
+
+        assert_eq!(position(text, 0), (1, 1));
+        assert_eq!(position(text, 6), (1, 7));
+        assert_eq!(position(text, 19), (2, 7));
+        assert_eq!(position(text, 36), (3, 10));
+    }
+
+    #[test]
+    fn test_line() {
+        let index = LineIndex::new("const a = 1;\r\nconst b = 2;\n");
+
+        assert_eq!(index.line(0), None);
+        assert_eq!(index.line(1), Some("const a = 1;"));
+        assert_eq!(index.line(2), Some("const b = 2;"));
+        assert_eq!(index.line(3), Some(""));
+        assert_eq!(index.line(4), None);
+    }
+
+    #[test]
+    fn test_position_counts_utf16_units() {
+        // 「日本」は2コード単位、絵文字はサロゲートペアで2コード単位
+        let text = "const s = '日本😀'; foo();";
+
+        assert_eq!(position(text, text.find("foo").unwrap()), (1, 19));
+    }
+}
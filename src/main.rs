@@ -39,10 +39,14 @@ struct Cli {
     #[arg(long)]
     interfaces: bool,
 
-    /// Detect functions
+    /// Detect functions (including custom hooks)
     #[arg(long)]
     functions: bool,
 
+    /// Detect custom hooks (`use*` functions that call other hooks)
+    #[arg(long)]
+    hooks: bool,
+
     /// Detect variables/constants
     #[arg(long)]
     variables: bool,
@@ -51,7 +55,31 @@ struct Cli {
     #[arg(long)]
     enums: bool,
 
-    /// Detect all element types
+    /// Detect classes
+    #[arg(long)]
+    classes: bool,
+
+    /// Report public class members never accessed from outside the class
+    #[arg(long)]
+    class_members: bool,
+
+    /// Detect non-exported top-level declarations unused within their file
+    #[arg(long)]
+    locals: bool,
+
+    /// Detect imported bindings never referenced in the importing file (opt-in)
+    #[arg(long)]
+    imports: bool,
+
+    /// Detect files that nothing imports (opt-in)
+    #[arg(long)]
+    files: bool,
+
+    /// Report unused enum members individually
+    #[arg(long)]
+    enum_members: bool,
+
+    /// Detect all element types, including the opt-in ones
     #[arg(long)]
     all: bool,
 }
@@ -72,7 +100,20 @@ fn main() -> Result<()> {
 
     // CLIオプションから設定を作成
     let custom_config =
-        if cli.all || cli.types || cli.interfaces || cli.functions || cli.variables || cli.enums {
+        if cli.all
+            || cli.types
+            || cli.interfaces
+            || cli.functions
+            || cli.hooks
+            || cli.variables
+            || cli.enums
+            || cli.classes
+            || cli.locals
+            || cli.imports
+            || cli.class_members
+            || cli.files
+            || cli.enum_members
+        {
             let mut config =
                 ts_unused_cleaner::load_config(cli.config.as_deref()).unwrap_or_default();
 
@@ -81,15 +122,30 @@ fn main() -> Result<()> {
                 config.detection_types.types = true;
                 config.detection_types.interfaces = true;
                 config.detection_types.functions = true;
+                config.detection_types.hooks = true;
                 config.detection_types.variables = true;
                 config.detection_types.enums = true;
+                config.detection_types.classes = true;
+                config.detection_types.class_members = true;
+                config.detection_types.locals = true;
+                config.detection_types.imports = true;
+                config.detection_types.files = true;
+                config.detection_types.enum_members = true;
             } else {
                 config.detection_types.components = true; // Always detect components
                 config.detection_types.types = cli.types;
                 config.detection_types.interfaces = cli.interfaces;
                 config.detection_types.functions = cli.functions;
+                // フックは関数の一種なので `--functions` でも検出する
+                config.detection_types.hooks = cli.hooks || cli.functions;
                 config.detection_types.variables = cli.variables;
                 config.detection_types.enums = cli.enums;
+                config.detection_types.classes = cli.classes;
+                config.detection_types.class_members = cli.class_members;
+                config.detection_types.locals = cli.locals;
+                config.detection_types.imports = cli.imports;
+                config.detection_types.files = cli.files;
+                config.detection_types.enum_members = cli.enum_members;
             }
 
             Some(config)
@@ -122,13 +178,24 @@ fn main() -> Result<()> {
     }
 
     // Strictモードでの終了処理
-    if cli.strict && !result.unused.is_empty() {
+    if cli.strict && (!result.unused.is_empty() || !result.unused_files.is_empty()) {
         if !cli.quiet {
+            // ファイル検出は opt-in なので、有効な場合のみファイル数を表示する
+            let files = if result.unused_files.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " and {} unused file{}",
+                    result.unused_files.len().to_string().red().bold(),
+                    if result.unused_files.len() == 1 { "" } else { "s" }
+                )
+            };
             eprintln!(
-                "\n{} Found {} unused element{}",
+                "\n{} Found {} unused element{}{}",
                 "❌".red(),
                 result.unused.len().to_string().red().bold(),
-                if result.unused.len() == 1 { "" } else { "s" }
+                if result.unused.len() == 1 { "" } else { "s" },
+                files
             );
         }
         process::exit(1);
@@ -181,10 +248,50 @@ mod tests {
             .stdout(predicate::str::contains("0.0.7"));
     }
 
+    #[test]
+    fn test_all_enables_opt_in_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("main.ts"),
+            "import { a } from './a';\nconst local = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(src.join("orphan.ts"), "export const orphan = 1;\n").unwrap();
+
+        let mut cmd = Command::cargo_bin("ts-unused-cleaner").unwrap();
+        cmd.current_dir(dir.path()).arg("--all");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("local (Local)"))
+            .stdout(predicate::str::contains("a (Import)"))
+            .stdout(predicate::str::contains("Found 1 unused file"));
+    }
+
+    #[test]
+    fn test_functions_flag_reports_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("hooks.ts"),
+            "import { useState } from 'react';\nexport function useCounter() { return useState(0); }\n",
+        )
+        .unwrap();
+
+        let mut cmd = Command::cargo_bin("ts-unused-cleaner").unwrap();
+        cmd.current_dir(dir.path()).arg("--functions");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("useCounter (Hook)"));
+    }
+
     #[test]
     fn test_quiet_output() {
         let mut cmd = Command::cargo_bin("ts-unused-cleaner").unwrap();
-        cmd.args(&["--quiet"]);
+        cmd.args(["--quiet"]);
         cmd.assert().success();
     }
 }
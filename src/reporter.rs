@@ -1,4 +1,4 @@
-use crate::types::{DetectionResult, ElementType};
+use crate::types::{DetectionResult, ElementInfo, ElementType};
 use colored::*;
 
 pub struct Reporter;
@@ -30,33 +30,95 @@ impl Reporter {
                 println!(
                     "{} {} ({})",
                     icon.red(),
-                    element.name.red().bold(),
+                    Self::display_name(element).red().bold(),
                     element.element_type.to_string().dimmed()
                 );
                 for file in &element.definition_files {
-                    println!("   📍 {}", file.dimmed());
+                    println!("   📍 {}", Self::location(file, element).dimmed());
+                }
+                for barrel in &element.reexported_from {
+                    println!("   ↪ re-exported from {}", barrel.dimmed());
                 }
                 println!();
             }
         }
 
+        if !result.locally_used.is_empty() {
+            println!(
+                "{} {}",
+                "🔓".yellow(),
+                format!(
+                    "Found {} export{} only used in {} own file (the export keyword can be removed):",
+                    result.locally_used.len(),
+                    if result.locally_used.len() == 1 { "" } else { "s" },
+                    if result.locally_used.len() == 1 { "its" } else { "their" }
+                )
+                .yellow()
+            );
+            println!();
+
+            for element in &result.locally_used {
+                let icon = Self::get_element_icon(&element.element_type);
+                println!(
+                    "{} {} ({})",
+                    icon.yellow(),
+                    Self::display_name(element).yellow().bold(),
+                    element.element_type.to_string().dimmed()
+                );
+                for file in &element.definition_files {
+                    println!("   📍 {}", Self::location(file, element).dimmed());
+                }
+                println!();
+            }
+        }
+
+        if !result.unused_files.is_empty() {
+            println!(
+                "{} {}",
+                "🗑️".red(),
+                format!(
+                    "Found {} unused file{}:",
+                    result.unused_files.len(),
+                    if result.unused_files.len() == 1 { "" } else { "s" }
+                )
+                .red()
+            );
+            for file in &result.unused_files {
+                println!("   📄 {}", file.dimmed());
+            }
+            println!();
+        }
+
         println!("\n📈 Statistics:");
         println!("   • Total elements: {}", result.total.to_string().bold());
         println!(
             "   • Used elements: {}",
             result.used.len().to_string().green().bold()
         );
+        if !result.locally_used.is_empty() {
+            println!(
+                "   • Only used in their own file: {}",
+                result.locally_used.len().to_string().yellow().bold()
+            );
+        }
         println!(
             "   • Unused elements: {}",
             result.unused.len().to_string().red().bold()
         );
 
+        let used_count = result.used.len() + result.locally_used.len();
         let usage_rate = if result.total > 0 {
-            (result.used.len() as f64 / result.total as f64 * 100.0).round() as usize
+            (used_count as f64 / result.total as f64 * 100.0).round() as usize
         } else {
             0
         };
         println!("   • Usage rate: {}%", usage_rate.to_string().cyan().bold());
+        if !result.unused_files.is_empty() {
+            println!(
+                "   • Unused files: {}",
+                result.unused_files.len().to_string().red().bold()
+            );
+        }
 
         // 要素タイプ別の統計を表示
         if !result.by_type.is_empty() {
@@ -80,6 +142,13 @@ impl Reporter {
             }
         }
 
+        if !result.notes.is_empty() {
+            println!("\nℹ️  Notes:");
+            for note in &result.notes {
+                println!("   • {}", note.dimmed());
+            }
+        }
+
         if verbose {
             Self::print_verbose_results(result);
         }
@@ -94,11 +163,19 @@ impl Reporter {
             println!("{}", "=".repeat(60));
 
             for element in &result.unused {
-                println!("\n{} {}", "❌".red(), element.name.red().bold());
-                println!(
-                    "   Definition: {}",
-                    element.definition_files.join(", ").dimmed()
-                );
+                println!("\n{} {}", "❌".red(), Self::display_name(element).red().bold());
+                let locations: Vec<String> = element
+                    .definition_files
+                    .iter()
+                    .map(|file| Self::location(file, element))
+                    .collect();
+                println!("   Definition: {}", locations.join(", ").dimmed());
+                if !element.reexported_from.is_empty() {
+                    println!(
+                        "   Re-exported from: {}",
+                        element.reexported_from.join(", ").dimmed()
+                    );
+                }
             }
         }
     }
@@ -120,6 +197,23 @@ impl Reporter {
         pb
     }
 
+    /// 要素の表示名（default エクスポートは `default (Button)` のように区別する）
+    fn display_name(element: &ElementInfo) -> String {
+        match &element.export_name {
+            Some(export_name) => format!("{} ({})", export_name, element.name),
+            None => element.name.clone(),
+        }
+    }
+
+    /// 定義位置を `path:line:col` 形式で返す（端末やIDEでクリックできる形）
+    fn location(file: &str, element: &ElementInfo) -> String {
+        match (element.line, element.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", file, line, column),
+            (Some(line), None) => format!("{}:{}", file, line),
+            _ => file.to_string(),
+        }
+    }
+
     /// 要素タイプに対応するアイコンを取得
     fn get_element_icon(element_type: &ElementType) -> &'static str {
         match element_type {
@@ -127,8 +221,14 @@ impl Reporter {
             ElementType::Type => "🔷",
             ElementType::Interface => "🔶",
             ElementType::Function => "🔵",
+            ElementType::Hook => "🪝",
             ElementType::Variable => "🟡",
             ElementType::Enum => "🟣",
+            ElementType::EnumMember => "🟪",
+            ElementType::Class => "🟤",
+            ElementType::ClassMember => "🟫",
+            ElementType::Local => "⚪",
+            ElementType::Import => "📥",
         }
     }
 }
@@ -136,7 +236,7 @@ impl Reporter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{DetectionResult, ElementInfo};
+    use crate::types::DetectionResult;
     use std::collections::HashMap;
 
     #[test]
@@ -147,14 +247,61 @@ mod tests {
                 name: "UsedComponent".to_string(),
                 element_type: ElementType::Component,
                 definition_files: vec!["src/used.tsx".to_string()],
+                line: None,
+                column: None,
+                export_name: None,
                 usages: None,
+                reexported_from: vec![],
             }],
+            locally_used: vec![],
             total: 1,
             by_type: HashMap::new(),
+            unused_files: vec![],
+            notes: vec![],
         };
 
         // 使用率100%のテスト
         assert_eq!(result.used.len(), 1);
         assert_eq!(result.total, 1);
     }
+
+    #[test]
+    fn test_location() {
+        let mut element = ElementInfo {
+            name: "UnusedButton".to_string(),
+            element_type: ElementType::Component,
+            definition_files: vec!["src/Button.tsx".to_string()],
+            line: None,
+            column: None,
+            export_name: None,
+            usages: None,
+            reexported_from: vec![],
+        };
+        assert_eq!(Reporter::location("src/Button.tsx", &element), "src/Button.tsx");
+
+        element.line = Some(12);
+        element.column = Some(14);
+        assert_eq!(
+            Reporter::location("src/Button.tsx", &element),
+            "src/Button.tsx:12:14"
+        );
+    }
+
+    #[test]
+    fn test_display_name() {
+        let mut element = ElementInfo {
+            name: "Button".to_string(),
+            element_type: ElementType::Component,
+            definition_files: vec!["src/Button.tsx".to_string()],
+            line: None,
+            column: None,
+            export_name: None,
+            usages: None,
+            reexported_from: vec![],
+        };
+        assert_eq!(Reporter::display_name(&element), "Button");
+
+        element.export_name = Some("default".to_string());
+        assert_eq!(Reporter::display_name(&element), "default (Button)");
+    }
 }
@@ -0,0 +1,394 @@
+use crate::tsconfig::{find_tsconfig_files, load_tsconfig, TsConfig};
+use std::path::{Component, Path, PathBuf};
+
+/// 解決時に試行する拡張子（優先順）
+const RESOLVE_EXTENSIONS: &[&str] = &[
+    ".ts", ".tsx", ".d.ts", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs",
+];
+
+/// NodeNext形式の `.js` 指定子を TypeScript ソースへ対応付ける
+const EXTENSION_ALIASES: &[(&str, &[&str])] = &[
+    (".js", &[".ts", ".tsx"]),
+    (".jsx", &[".tsx"]),
+    (".mjs", &[".mts"]),
+    (".cjs", &[".cts"]),
+];
+
+/// import指定子の解決結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// プロジェクト内のファイルに解決できた
+    File(String),
+    /// node_modules のパッケージなど外部モジュール
+    External,
+    /// 解決できなかった
+    Unresolved,
+}
+
+/// import指定子を実際のファイルへ解決する
+#[derive(Debug, Clone, Default)]
+pub struct ModuleResolver {
+    /// `baseUrl` / `paths` の解決に使う tsconfig（ディレクトリごと）
+    tsconfigs: Vec<TsConfig>,
+}
+
+impl ModuleResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// tsconfig を指定して作成
+    pub fn with_tsconfigs(tsconfigs: Vec<TsConfig>) -> Self {
+        Self { tsconfigs }
+    }
+
+    /// プロジェクトルートと検索ディレクトリの tsconfig.json を読み込んで作成
+    pub fn from_search_dirs(search_dirs: &[String]) -> Self {
+        let tsconfigs = find_tsconfig_files(search_dirs)
+            .into_iter()
+            .filter_map(|path| match load_tsconfig(&path) {
+                Ok(tsconfig) => Some(tsconfig),
+                Err(e) => {
+                    eprintln!("⚠️  Failed to load {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect();
+        Self::with_tsconfigs(tsconfigs)
+    }
+
+    /// `from_file` からの `specifier` を解決する
+    pub fn resolve(&self, from_file: &str, specifier: &str) -> Resolution {
+        let dir = Path::new(from_file)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        self.resolve_from_dir(dir, specifier)
+    }
+
+    /// ディレクトリ `dir` を基準に `specifier` を解決する
+    pub fn resolve_from_dir(&self, dir: &Path, specifier: &str) -> Resolution {
+        if is_relative_specifier(specifier) {
+            return match self.resolve_path(&dir.join(specifier)) {
+                Some(path) => Resolution::File(path),
+                None => Resolution::Unresolved,
+            };
+        }
+
+        if let Some(tsconfig) = self.tsconfig_for(dir) {
+            if let Some(path) = self.resolve_with_tsconfig(tsconfig, specifier) {
+                return Resolution::File(path);
+            }
+        }
+
+        if is_package_installed(dir, specifier) {
+            return Resolution::External;
+        }
+
+        Resolution::Unresolved
+    }
+
+    /// ファイルに適用される（そのファイルを含む最も深い）tsconfig を取得
+    pub fn tsconfig_for_file(&self, file: &str) -> Option<&TsConfig> {
+        self.tsconfig_for(Path::new(file).parent().unwrap_or_else(|| Path::new("")))
+    }
+
+    /// `dir` を含む最も深い tsconfig を取得
+    fn tsconfig_for(&self, dir: &Path) -> Option<&TsConfig> {
+        let dir = normalize_path(dir);
+        self.tsconfigs
+            .iter()
+            .filter(|tsconfig| dir.starts_with(&tsconfig.config_dir))
+            .max_by_key(|tsconfig| tsconfig.config_dir.components().count())
+    }
+
+    /// `paths` と `baseUrl` で非相対指定子を解決する
+    fn resolve_with_tsconfig(&self, tsconfig: &TsConfig, specifier: &str) -> Option<String> {
+        if let Some((targets, matched)) = match_paths(&tsconfig.paths, specifier) {
+            let base = tsconfig.paths_base();
+            for target in targets {
+                let substituted = target.replacen('*', matched, 1);
+                if let Some(path) = self.resolve_path(&base.join(substituted)) {
+                    return Some(path);
+                }
+            }
+        }
+
+        let base_url = tsconfig.base_url.as_ref()?;
+        self.resolve_path(&base_url.join(specifier))
+    }
+
+    /// ファイルまたはディレクトリとしてパスを解決する
+    fn resolve_path(&self, base: &Path) -> Option<String> {
+        let base = normalize_path(base);
+        self.resolve_as_file(&base)
+            .or_else(|| self.resolve_as_directory(&base))
+            .map(|path| path.to_string_lossy().to_string())
+    }
+
+    fn resolve_as_file(&self, base: &Path) -> Option<PathBuf> {
+        let base_str = base.to_string_lossy();
+
+        // `./foo.js` → `./foo.ts` (NodeNext)
+        for (from, targets) in EXTENSION_ALIASES {
+            if let Some(stem) = base_str.strip_suffix(from) {
+                for target in *targets {
+                    let candidate = PathBuf::from(format!("{}{}", stem, target));
+                    if candidate.is_file() {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+
+        if base.is_file() {
+            return Some(base.to_path_buf());
+        }
+
+        RESOLVE_EXTENSIONS
+            .iter()
+            .map(|ext| PathBuf::from(format!("{}{}", base_str, ext)))
+            .find(|candidate| candidate.is_file())
+    }
+
+    fn resolve_as_directory(&self, base: &Path) -> Option<PathBuf> {
+        if !base.is_dir() {
+            return None;
+        }
+
+        RESOLVE_EXTENSIONS
+            .iter()
+            .map(|ext| base.join(format!("index{}", ext)))
+            .find(|candidate| candidate.is_file())
+    }
+}
+
+/// `paths` のパターンに一致する置換先と `*` に一致した部分を返す
+///
+/// TypeScript と同様に、完全一致を優先し、次に最も長い接頭辞を持つパターンを選ぶ
+fn match_paths<'a, 'b>(
+    paths: &'a [(String, Vec<String>)],
+    specifier: &'b str,
+) -> Option<(&'a [String], &'b str)> {
+    if let Some((_, targets)) = paths.iter().find(|(pattern, _)| pattern == specifier) {
+        return Some((targets, ""));
+    }
+
+    paths
+        .iter()
+        .filter_map(|(pattern, targets)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), targets.as_slice(), matched))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len)
+        .map(|(_, targets, matched)| (targets, matched))
+}
+
+/// 相対指定子かどうか
+pub fn is_relative_specifier(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier.starts_with('/')
+}
+
+/// 指定子のパッケージ名部分を取得（`@scope/pkg/sub` → `@scope/pkg`）
+fn package_name(specifier: &str) -> &str {
+    let mut parts = specifier.splitn(3, '/');
+    let first = parts.next().unwrap_or("");
+    if first.starts_with('@') {
+        match parts.next() {
+            Some(second) => &specifier[..first.len() + 1 + second.len()],
+            None => first,
+        }
+    } else {
+        first
+    }
+}
+
+/// `dir` から上位ディレクトリを辿り、node_modules にパッケージが存在するか確認
+fn is_package_installed(dir: &Path, specifier: &str) -> bool {
+    let name = package_name(specifier);
+    if name.is_empty() {
+        return false;
+    }
+
+    let start = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    start
+        .ancestors()
+        .any(|dir| dir.join("node_modules").join(name).exists())
+}
+
+/// `.` と `..` を字句的に取り除いてパスを正規化する
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let can_pop = matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                );
+                if can_pop {
+                    normalized.pop();
+                } else if !normalized.has_root() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("./src/pages/../components/button")),
+            PathBuf::from("src/components/button")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../shared/./utils")),
+            PathBuf::from("../shared/utils")
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_with_extension_probing() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        touch(&root.join("src/components/button.tsx"));
+        touch(&root.join("src/utils/index.ts"));
+        touch(&root.join("src/lib/format.ts"));
+
+        let resolver = ModuleResolver::new();
+        let from = root.join("src/pages/home.tsx");
+        let from = from.to_str().unwrap();
+
+        assert_eq!(
+            resolver.resolve(from, "../components/button"),
+            Resolution::File(
+                normalize_path(&root.join("src/components/button.tsx"))
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            resolver.resolve(from, "../utils"),
+            Resolution::File(
+                normalize_path(&root.join("src/utils/index.ts"))
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            resolver.resolve(from, "../lib/format.js"),
+            Resolution::File(
+                normalize_path(&root.join("src/lib/format.ts"))
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert_eq!(resolver.resolve(from, "../missing"), Resolution::Unresolved);
+    }
+
+    #[test]
+    fn test_resolve_installed_package_as_external() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("node_modules/@scope/ui")).unwrap();
+        fs::create_dir_all(root.join("node_modules/react")).unwrap();
+
+        let resolver = ModuleResolver::new();
+        let from = root.join("src/app.tsx");
+        let from = from.to_str().unwrap();
+
+        assert_eq!(resolver.resolve(from, "react"), Resolution::External);
+        assert_eq!(
+            resolver.resolve(from, "@scope/ui/button"),
+            Resolution::External
+        );
+        assert_eq!(
+            resolver.resolve(from, "@/components"),
+            Resolution::Unresolved
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_tsconfig_paths() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        touch(&root.join("src/components/Button.tsx"));
+        touch(&root.join("shared/utils/index.ts"));
+        touch(&root.join("src/lib/date.ts"));
+
+        let tsconfig = TsConfig {
+            config_dir: normalize_path(root),
+            base_url: Some(normalize_path(&root.join("src"))),
+            paths: vec![
+                ("@/*".to_string(), vec!["./*".to_string()]),
+                ("~shared/*".to_string(), vec!["../shared/*".to_string()]),
+            ],
+            paths_dir: normalize_path(root),
+            ..Default::default()
+        };
+        let resolver = ModuleResolver::with_tsconfigs(vec![tsconfig]);
+        let from = root.join("src/app.tsx");
+        let from = from.to_str().unwrap();
+        let expected =
+            |p: &str| Resolution::File(normalize_path(&root.join(p)).to_string_lossy().to_string());
+
+        assert_eq!(
+            resolver.resolve(from, "@/components/Button"),
+            expected("src/components/Button.tsx")
+        );
+        assert_eq!(
+            resolver.resolve(from, "~shared/utils"),
+            expected("shared/utils/index.ts")
+        );
+        // baseUrl からの解決
+        assert_eq!(
+            resolver.resolve(from, "lib/date"),
+            expected("src/lib/date.ts")
+        );
+        assert_eq!(resolver.resolve(from, "@/missing"), Resolution::Unresolved);
+    }
+
+    #[test]
+    fn test_match_paths_prefers_longest_prefix() {
+        let paths = vec![
+            ("@/*".to_string(), vec!["src/*".to_string()]),
+            ("@/ui/*".to_string(), vec!["packages/ui/*".to_string()]),
+            ("config".to_string(), vec!["config/index".to_string()]),
+        ];
+
+        let (targets, matched) = match_paths(&paths, "@/ui/Button").unwrap();
+        assert_eq!(targets, ["packages/ui/*".to_string()]);
+        assert_eq!(matched, "Button");
+
+        let (targets, _) = match_paths(&paths, "config").unwrap();
+        assert_eq!(targets, ["config/index".to_string()]);
+
+        assert!(match_paths(&paths, "react").is_none());
+    }
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("react"), "react");
+        assert_eq!(package_name("lodash/debounce"), "lodash");
+        assert_eq!(package_name("@scope/pkg/sub"), "@scope/pkg");
+    }
+}
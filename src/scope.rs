@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use swc_common::{Globals, Mark, SyntaxContext, GLOBALS};
+use swc_ecma_ast::*;
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+/// swc の resolver による識別子の束縛解決の結果
+///
+/// resolver は束縛ごとに識別子の `SyntaxContext` を付け替えるので、同名でも
+/// 関数・ブロック・クラス式・namespace の内側で宣言された束縛への参照はトップレベルと区別できる
+#[derive(Debug, Clone, Copy)]
+pub struct Scopes {
+    /// トップレベルの宣言・importの束縛
+    top_level: SyntaxContext,
+    /// どの宣言にも解決されない参照（グローバル）
+    unresolved: SyntaxContext,
+}
+
+impl Scopes {
+    /// モジュールに resolver を適用する
+    pub fn resolve(module: &mut Module, typescript: bool) -> Self {
+        GLOBALS.set(&Globals::new(), || {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            module.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, typescript));
+            let scopes = Self {
+                top_level: SyntaxContext::empty().apply_mark(top_level_mark),
+                unresolved: SyntaxContext::empty().apply_mark(unresolved_mark),
+            };
+            module.visit_mut_with(&mut InferResolver { scopes });
+            scopes
+        })
+    }
+
+    /// 識別子が関数・ブロックなど内側のスコープの束縛を指すかどうか
+    ///
+    /// トップレベルの束縛、グローバル、resolver が扱わない位置の識別子（`SyntaxContext` が空）は false
+    pub fn is_scoped(&self, ident: &Ident) -> bool {
+        let ctxt = ident.span.ctxt;
+        ctxt != self.top_level && ctxt != self.unresolved && ctxt != SyntaxContext::empty()
+    }
+}
+
+/// 条件型の `infer X` を `extends` 節と真の分岐だけの束縛にする
+///
+/// resolver は `infer X` を外側の `X` への参照として解決してしまうため、その分を補う
+struct InferResolver {
+    scopes: Scopes,
+}
+
+impl VisitMut for InferResolver {
+    fn visit_mut_ts_conditional_type(&mut self, conditional: &mut TsConditionalType) {
+        let mut collector = InferCollector { names: HashSet::new() };
+        conditional.extends_type.visit_with(&mut collector);
+        if !collector.names.is_empty() {
+            let mut rebinder = Rebinder {
+                scopes: self.scopes,
+                names: collector.names,
+                ctxt: SyntaxContext::empty().apply_mark(Mark::new()),
+            };
+            conditional.extends_type.visit_mut_with(&mut rebinder);
+            conditional.true_type.visit_mut_with(&mut rebinder);
+        }
+        // 入れ子の条件型の `infer` は外側より内側の束縛が優先される
+        conditional.visit_mut_children_with(self);
+    }
+}
+
+struct InferCollector {
+    names: HashSet<String>,
+}
+
+impl Visit for InferCollector {
+    fn visit_ts_infer_type(&mut self, infer: &TsInferType) {
+        self.names.insert(infer.type_param.name.sym.to_string());
+        infer.visit_children_with(self);
+    }
+
+    // 入れ子の条件型の `extends` 節・分岐の `infer` はその条件型のもの
+    fn visit_ts_conditional_type(&mut self, conditional: &TsConditionalType) {
+        conditional.check_type.visit_with(self);
+    }
+}
+
+/// 内側のスコープの束縛に解決されていない `names` の識別子を `ctxt` の束縛に付け替える
+struct Rebinder {
+    scopes: Scopes,
+    names: HashSet<String>,
+    ctxt: SyntaxContext,
+}
+
+impl VisitMut for Rebinder {
+    fn visit_mut_ident(&mut self, ident: &mut Ident) {
+        if self.names.contains(ident.sym.as_ref()) && !self.scopes.is_scoped(ident) {
+            ident.span.ctxt = self.ctxt;
+        }
+    }
+}
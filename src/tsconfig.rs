@@ -0,0 +1,397 @@
+use crate::resolver::normalize_path;
+use crate::types::DetectorError;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `extends` の循環を防ぐための最大深さ
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// モジュール解決・JSXの変換に関係する compilerOptions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsConfig {
+    /// tsconfig.json が置かれているディレクトリ
+    pub config_dir: PathBuf,
+    /// 解決済みの `baseUrl`
+    pub base_url: Option<PathBuf>,
+    /// `paths` のパターンと置換先
+    pub paths: Vec<(String, Vec<String>)>,
+    /// `paths` を定義した tsconfig のディレクトリ
+    pub paths_dir: PathBuf,
+    /// 解決済みの `outDir`
+    pub out_dir: Option<PathBuf>,
+    /// 解決済みの `rootDir`
+    pub root_dir: Option<PathBuf>,
+    /// `jsx`（`react-jsx` など）
+    pub jsx: Option<String>,
+}
+
+impl TsConfig {
+    /// `paths` の置換先の基準ディレクトリ（`baseUrl` がなければ定義元）
+    pub fn paths_base(&self) -> &Path {
+        self.base_url.as_deref().unwrap_or(&self.paths_dir)
+    }
+
+    /// JSXを自動ランタイム（`react/jsx-runtime`）で変換するかどうか
+    pub fn uses_automatic_jsx(&self) -> bool {
+        matches!(self.jsx.as_deref(), Some("react-jsx" | "react-jsxdev"))
+    }
+}
+
+/// tsconfig.json を `extends` を辿りながら読み込む
+pub fn load_tsconfig(path: &Path) -> Result<TsConfig, DetectorError> {
+    let config_dir = config_dir_of(path);
+    let mut config = TsConfig {
+        paths_dir: config_dir.clone(),
+        config_dir,
+        ..Default::default()
+    };
+    apply_tsconfig(path, &mut config, 0)?;
+    Ok(config)
+}
+
+/// `path` の設定を読み込み、基底設定 → 自身の順に `config` へ反映する
+fn apply_tsconfig(path: &Path, config: &mut TsConfig, depth: usize) -> Result<(), DetectorError> {
+    if depth > MAX_EXTENDS_DEPTH {
+        return Err(DetectorError::Config {
+            message: format!("tsconfig extends chain is too deep: {}", path.display()),
+        });
+    }
+
+    let content = fs::read_to_string(path).map_err(|_| DetectorError::FileNotFound {
+        path: path.to_string_lossy().to_string(),
+    })?;
+    let json: Value = serde_json::from_str(&strip_json_comments(&content))?;
+    let dir = config_dir_of(path);
+
+    // extends は文字列または配列（TypeScript 5.0+）
+    let extends: Vec<&str> = match json.get("extends") {
+        Some(Value::String(s)) => vec![s.as_str()],
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    for base in extends {
+        match resolve_extends(&dir, base) {
+            Some(base_path) => apply_tsconfig(&base_path, config, depth + 1)?,
+            None => {
+                return Err(DetectorError::FileNotFound {
+                    path: format!("{} (extended from {})", base, path.display()),
+                })
+            }
+        }
+    }
+
+    let Some(options) = json.get("compilerOptions") else {
+        return Ok(());
+    };
+
+    // 相対パスは定義元の tsconfig を基準に解決する
+    if let Some(base_url) = options.get("baseUrl").and_then(|v| v.as_str()) {
+        config.base_url = Some(normalize_path(&dir.join(base_url)));
+    }
+
+    if let Some(out_dir) = options.get("outDir").and_then(|v| v.as_str()) {
+        config.out_dir = Some(normalize_path(&dir.join(out_dir)));
+    }
+    if let Some(root_dir) = options.get("rootDir").and_then(|v| v.as_str()) {
+        config.root_dir = Some(normalize_path(&dir.join(root_dir)));
+    }
+    if let Some(jsx) = options.get("jsx").and_then(|v| v.as_str()) {
+        config.jsx = Some(jsx.to_string());
+    }
+
+    if let Some(paths) = options.get("paths").and_then(|v| v.as_object()) {
+        config.paths = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|t| t.as_str())
+                    .map(|t| t.to_string())
+                    .collect();
+                (pattern.clone(), targets)
+            })
+            .collect();
+        config.paths_dir = dir;
+    }
+
+    Ok(())
+}
+
+/// `extends` の値を tsconfig ファイルのパスへ解決する
+fn resolve_extends(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let with_json = |path: PathBuf| -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path);
+        }
+        let json = PathBuf::from(format!("{}.json", path.to_string_lossy()));
+        if json.is_file() {
+            return Some(json);
+        }
+        let nested = path.join("tsconfig.json");
+        nested.is_file().then_some(nested)
+    };
+
+    if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+        return with_json(normalize_path(&dir.join(specifier)));
+    }
+
+    // パッケージ（例: `@tsconfig/node18/tsconfig.json`）は node_modules から探す
+    let start = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    start
+        .ancestors()
+        .find_map(|ancestor| with_json(ancestor.join("node_modules").join(specifier)))
+}
+
+fn config_dir_of(path: &Path) -> PathBuf {
+    normalize_path(path.parent().unwrap_or_else(|| Path::new("")))
+}
+
+/// JSONC（コメント・末尾カンマ）を標準JSONへ変換する
+pub fn strip_json_comments(content: &str) -> String {
+    remove_trailing_commas(&remove_comments(content))
+}
+
+fn remove_comments(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut output = String::with_capacity(content.len());
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if in_string {
+            output.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                output.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match (c, chars.get(i + 1)) {
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            _ => {
+                in_string = c == '"';
+                output.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    output
+}
+
+fn remove_trailing_commas(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut output = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+            output.push(c);
+            continue;
+        }
+
+        if c == ',' {
+            // 閉じ括弧の直前のカンマは削除
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+
+        in_string = c == '"';
+        output.push(c);
+    }
+
+    output
+}
+
+/// プロジェクトルートと各検索ディレクトリに対応する tsconfig.json を探す
+pub fn find_tsconfig_files(search_dirs: &[String]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+
+    let root = PathBuf::from("tsconfig.json");
+    if root.is_file() {
+        found.push(root);
+    }
+
+    for dir in search_dirs {
+        let dir = normalize_path(Path::new(dir));
+        let nearest = dir
+            .ancestors()
+            .map(|ancestor| ancestor.join("tsconfig.json"))
+            .find(|candidate| candidate.is_file());
+        if let Some(path) = nearest {
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_strip_json_comments() {
+        let input = r#"{
+  // line comment
+  "a": "http://example.com", /* block */
+  "b": [1, 2, /* trailing */],
+  "c": "quoted \" // still a string",
+}"#;
+        let value: Value = serde_json::from_str(&strip_json_comments(input)).unwrap();
+        assert_eq!(value["a"], "http://example.com");
+        assert_eq!(value["b"].as_array().unwrap().len(), 2);
+        assert_eq!(value["c"], "quoted \" // still a string");
+    }
+
+    #[test]
+    fn test_load_tsconfig_with_paths() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("tsconfig.json"),
+            r#"{
+  "compilerOptions": {
+    "baseUrl": "./src",
+    "paths": { "@/*": ["./*"] }
+  }
+}"#,
+        );
+
+        let config = load_tsconfig(&root.join("tsconfig.json")).unwrap();
+        assert_eq!(config.base_url, Some(normalize_path(&root.join("src"))));
+        assert_eq!(config.paths_base(), normalize_path(&root.join("src")));
+        assert_eq!(
+            config.paths,
+            vec![("@/*".to_string(), vec!["./*".to_string()])]
+        );
+    }
+
+    #[test]
+    fn test_load_tsconfig_follows_extends() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("node_modules/@company/tsconfig/base.json"),
+            r#"{ "compilerOptions": { "strict": true } }"#,
+        );
+        write(
+            &root.join("configs/paths.json"),
+            r#"{ "compilerOptions": { "paths": { "~shared/*": ["../shared/*"] } } }"#,
+        );
+        write(
+            &root.join("app/tsconfig.json"),
+            r#"{ "extends": ["@company/tsconfig/base.json", "../configs/paths"] }"#,
+        );
+
+        let config = load_tsconfig(&root.join("app/tsconfig.json")).unwrap();
+        assert_eq!(config.base_url, None);
+        // baseUrl がない場合、paths は定義元の tsconfig を基準にする
+        assert_eq!(config.paths_base(), normalize_path(&root.join("configs")));
+        assert_eq!(config.config_dir, normalize_path(&root.join("app")));
+    }
+
+    #[test]
+    fn test_load_tsconfig_output_dirs() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("tsconfig.base.json"),
+            r#"{ "compilerOptions": { "outDir": "./dist" } }"#,
+        );
+        write(
+            &root.join("packages/ui/tsconfig.json"),
+            r#"{ "extends": "../../tsconfig.base.json", "compilerOptions": { "rootDir": "src" } }"#,
+        );
+
+        let config = load_tsconfig(&root.join("packages/ui/tsconfig.json")).unwrap();
+        // 相対パスは定義元の tsconfig を基準にする
+        assert_eq!(config.out_dir, Some(normalize_path(&root.join("dist"))));
+        assert_eq!(config.root_dir, Some(normalize_path(&root.join("packages/ui/src"))));
+    }
+
+    #[test]
+    fn test_load_tsconfig_inherits_base_url() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("tsconfig.base.json"),
+            r#"{ "compilerOptions": { "baseUrl": "." } }"#,
+        );
+        write(
+            &root.join("packages/web/tsconfig.json"),
+            r#"{ "extends": "../../tsconfig.base.json" }"#,
+        );
+
+        let config = load_tsconfig(&root.join("packages/web/tsconfig.json")).unwrap();
+        assert_eq!(config.base_url, Some(normalize_path(root)));
+    }
+
+    #[test]
+    fn test_load_tsconfig_jsx_runtime() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("tsconfig.base.json"),
+            r#"{ "compilerOptions": { "jsx": "react-jsx" } }"#,
+        );
+        write(
+            &root.join("packages/web/tsconfig.json"),
+            r#"{ "extends": "../../tsconfig.base.json" }"#,
+        );
+        write(
+            &root.join("packages/legacy/tsconfig.json"),
+            r#"{ "extends": "../../tsconfig.base.json", "compilerOptions": { "jsx": "react" } }"#,
+        );
+
+        let web = load_tsconfig(&root.join("packages/web/tsconfig.json")).unwrap();
+        assert!(web.uses_automatic_jsx());
+        let legacy = load_tsconfig(&root.join("packages/legacy/tsconfig.json")).unwrap();
+        assert!(!legacy.uses_automatic_jsx());
+    }
+
+    #[test]
+    fn test_missing_extends_is_an_error() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            &root.join("tsconfig.json"),
+            r#"{ "extends": "./missing.json" }"#,
+        );
+
+        assert!(load_tsconfig(&root.join("tsconfig.json")).is_err());
+    }
+}
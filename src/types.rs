@@ -8,6 +8,29 @@ pub struct Config {
     /// 除外するファイル/ディレクトリのパターン
     #[serde(default = "default_exclude_patterns")]
     pub exclude_patterns: Vec<String>,
+    /// 解析対象の拡張子
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<String>,
+    /// .js ファイルでJSXを有効にするか
+    #[serde(default = "default_true")]
+    pub jsx_in_js: bool,
+    /// エントリーポイントのglobパターン（importされなくても使用中とみなす）
+    #[serde(default = "default_entry_points")]
+    pub entry_points: Vec<String>,
+    /// 公開APIとなるファイルのglobパターン（エクスポートは常に使用中とみなす）
+    #[serde(default)]
+    pub public_entry_points: Vec<String>,
+    /// package.json から求めたエントリーのglobパターン（公開APIも兼ねる）
+    ///
+    /// パッケージのディレクトリを基準とし、任意のディレクトリ配下には一致させない
+    #[serde(skip)]
+    pub package_entry_points: Vec<String>,
+    /// `<script src>` をエントリーポイントとして読み込むHTMLファイル
+    #[serde(default = "default_html_entry_files")]
+    pub html_entry_files: Vec<String>,
+    /// 命名規則（PascalCase / camelCase / CONSTANT_CASE）に合わない宣言を検出対象から外すか
+    #[serde(default)]
+    pub naming_filter: bool,
     /// 検出する要素の種類
     #[serde(default)]
     pub detection_types: DetectionTypes,
@@ -25,10 +48,31 @@ pub struct DetectionTypes {
     pub interfaces: bool,
     /// 関数を検出するか
     pub functions: bool,
+    /// カスタムフック（`use[A-Z]` で始まり、他のフックを呼び出す関数）を検出するか
+    #[serde(default = "default_true")]
+    pub hooks: bool,
     /// 変数/定数を検出するか
     pub variables: bool,
     /// enumを検出するか
     pub enums: bool,
+    /// クラスを検出するか
+    #[serde(default = "default_true")]
+    pub classes: bool,
+    /// 公開クラスメンバー（メソッド・プロパティ）を検出するか
+    #[serde(default)]
+    pub class_members: bool,
+    /// エクスポートされていないトップレベル宣言を検出するか
+    #[serde(default)]
+    pub locals: bool,
+    /// 使われていないimportを検出するか
+    #[serde(default)]
+    pub imports: bool,
+    /// どこからもimportされないファイルを検出するか
+    #[serde(default)]
+    pub files: bool,
+    /// enumのメンバー単位で未使用を検出するか
+    #[serde(default)]
+    pub enum_members: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,7 +93,11 @@ pub struct ElementUsage {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Usage {
+    /// 1始まりの行番号
     pub line: usize,
+    /// 1始まりの列番号（UTF-16のコード単位）
+    #[serde(default = "default_column")]
+    pub column: usize,
     pub context: String,
 }
 
@@ -58,7 +106,19 @@ pub struct ElementInfo {
     pub name: String,
     pub element_type: ElementType,
     pub definition_files: Vec<String>,
+    /// 定義の行番号（1始まり）
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// 定義の列番号（1始まり、UTF-16のコード単位）
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// `name` と異なるエクスポート名（default エクスポートなら `default`）
+    #[serde(default)]
+    pub export_name: Option<String>,
     pub usages: Option<Vec<ElementUsage>>,
+    /// この要素を再エクスポートしているファイル（バレル）
+    #[serde(default)]
+    pub reexported_from: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,16 +127,31 @@ pub enum ElementType {
     Type,
     Interface,
     Function,
+    Hook,
     Variable,
     Enum,
+    EnumMember,
+    Class,
+    ClassMember,
+    Local,
+    Import,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionResult {
     pub unused: Vec<ElementInfo>,
     pub used: Vec<ElementInfo>,
+    /// 定義ファイル内でのみ使われているエクスポート（export キーワードが不要）
+    #[serde(default)]
+    pub locally_used: Vec<ElementInfo>,
     pub total: usize,
     pub by_type: HashMap<ElementType, DetectionStats>,
+    /// どこからもimportされていないファイル
+    #[serde(default)]
+    pub unused_files: Vec<String>,
+    /// 解析上の注意事項（保守的に使用扱いにした理由など）
+    #[serde(default)]
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +166,38 @@ pub struct ElementMap {
     pub definitions: HashMap<String, (ElementType, Vec<String>)>,
 }
 
+/// デフォルトの解析対象拡張子を返す
+pub fn default_extensions() -> Vec<String> {
+    [".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs"]
+        .iter()
+        .map(|ext| ext.to_string())
+        .collect()
+}
+
+/// デフォルトのエントリーポイントを返す
+pub fn default_entry_points() -> Vec<String> {
+    vec![
+        "src/main.{ts,tsx,mts,js,jsx,mjs,cjs}".to_string(),
+        "src/index.{ts,tsx,mts,js,jsx,mjs,cjs}".to_string(),
+        "app/**/{page,layout,template,loading,error,not-found,route,default}.{ts,tsx,js,jsx}"
+            .to_string(),
+        "*.config.{ts,mts,cts,js,mjs,cjs}".to_string(),
+    ]
+}
+
+/// エントリーポイントを探すデフォルトのHTMLファイルを返す（Vite・Parcel等）
+pub fn default_html_entry_files() -> Vec<String> {
+    vec!["index.html".to_string(), "src/index.html".to_string()]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_column() -> usize {
+    1
+}
+
 /// デフォルトの除外パターンを返す
 pub fn default_exclude_patterns() -> Vec<String> {
     vec![
@@ -129,6 +236,13 @@ impl Default for Config {
         Self {
             search_dirs: vec!["src".to_string()],
             exclude_patterns: default_exclude_patterns(),
+            extensions: default_extensions(),
+            jsx_in_js: true,
+            entry_points: default_entry_points(),
+            public_entry_points: vec![],
+            package_entry_points: vec![],
+            html_entry_files: default_html_entry_files(),
+            naming_filter: false,
             detection_types: DetectionTypes::default(),
             ci: Some(CiConfig {
                 max_unused_elements: 5,
@@ -146,8 +260,15 @@ impl Default for DetectionTypes {
             types: true,
             interfaces: true,
             functions: true,
+            hooks: true,
             variables: true,
             enums: true,
+            classes: true,
+            class_members: false,
+            locals: false,
+            imports: false,
+            files: false,
+            enum_members: false,
         }
     }
 }
@@ -159,8 +280,14 @@ impl std::fmt::Display for ElementType {
             ElementType::Type => write!(f, "Type"),
             ElementType::Interface => write!(f, "Interface"),
             ElementType::Function => write!(f, "Function"),
+            ElementType::Hook => write!(f, "Hook"),
             ElementType::Variable => write!(f, "Variable"),
             ElementType::Enum => write!(f, "Enum"),
+            ElementType::EnumMember => write!(f, "Enum member"),
+            ElementType::Class => write!(f, "Class"),
+            ElementType::ClassMember => write!(f, "Class member"),
+            ElementType::Local => write!(f, "Local"),
+            ElementType::Import => write!(f, "Import"),
         }
     }
 }
@@ -210,6 +337,9 @@ mod tests {
             .exclude_patterns
             .contains(&"node_modules".to_string()));
         assert!(config.exclude_patterns.contains(&"*.test.ts".to_string()));
+        assert!(config.extensions.contains(&".tsx".to_string()));
+        assert!(config.extensions.contains(&".cjs".to_string()));
+        assert!(config.jsx_in_js);
         assert!(config.detection_types.components);
         assert!(config.detection_types.types);
         assert!(config.detection_types.interfaces);
@@ -238,6 +368,12 @@ mod tests {
         assert_eq!(ElementType::Function.to_string(), "Function");
         assert_eq!(ElementType::Variable.to_string(), "Variable");
         assert_eq!(ElementType::Enum.to_string(), "Enum");
+        assert_eq!(ElementType::EnumMember.to_string(), "Enum member");
+        assert_eq!(ElementType::Class.to_string(), "Class");
+        assert_eq!(ElementType::ClassMember.to_string(), "Class member");
+        assert_eq!(ElementType::Local.to_string(), "Local");
+        assert_eq!(ElementType::Import.to_string(), "Import");
+        assert_eq!(ElementType::Hook.to_string(), "Hook");
     }
 
     #[test]
@@ -249,5 +385,7 @@ mod tests {
         assert!(detection_types.functions);
         assert!(detection_types.variables);
         assert!(detection_types.enums);
+        assert!(!detection_types.imports);
+        assert!(!detection_types.files);
     }
 }